@@ -0,0 +1,55 @@
+use borsh::BorshDeserialize;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use unc_account_id::AccountId;
+
+mod common;
+use common::Shape;
+
+/// 10k valid Account IDs of mixed lengths, serialized back to back.
+fn corpus() -> Vec<u8> {
+    let mut buf = Vec::new();
+    let shapes = [
+        Shape::Short,
+        Shape::Nested,
+        Shape::NearImplicit,
+        Shape::EthImplicit,
+    ];
+    for account_id in common::corpus(10_000, &shapes) {
+        let account_id: AccountId = account_id.parse().unwrap();
+        borsh::to_writer(&mut buf, &account_id).unwrap();
+    }
+    buf
+}
+
+/// Deserializes through `String`, then validates, as `AccountId` used to.
+fn deserialize_via_string(rd: &mut &[u8]) -> Box<str> {
+    let account_id = Box::<str>::deserialize_reader(rd).unwrap();
+    AccountId::validate(&account_id).unwrap();
+    account_id
+}
+
+fn bench_deserialize(c: &mut Criterion) {
+    let corpus = corpus();
+
+    let mut group = c.benchmark_group("borsh_deserialize_10k");
+    group.bench_function("account_id", |b| {
+        b.iter(|| {
+            let mut rd = black_box(corpus.as_slice());
+            while !rd.is_empty() {
+                black_box(AccountId::deserialize(&mut rd).unwrap());
+            }
+        })
+    });
+    group.bench_function("string_then_validate", |b| {
+        b.iter(|| {
+            let mut rd = black_box(corpus.as_slice());
+            while !rd.is_empty() {
+                black_box(deserialize_via_string(&mut rd));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_deserialize);
+criterion_main!(benches);
@@ -0,0 +1,40 @@
+//! Account IDs shared by the benchmarks.
+
+// Not every benchmark uses every shape.
+#![allow(dead_code)]
+
+/// The kinds of Account IDs the benchmark corpora are made of.
+#[derive(Clone, Copy)]
+pub enum Shape {
+    /// `a{i}.near`
+    Short,
+    /// `app{i}.alice-{i % 97}.testnet`
+    Nested,
+    /// `sub_{i % 1009}.app-{i}.near`
+    Underscored,
+    /// A 64 hex digit NEAR-implicit account.
+    NearImplicit,
+    /// A `0x`-prefixed 40 hex digit ETH-implicit account.
+    EthImplicit,
+    /// `app{i}..near`, which has a redundant separator.
+    Invalid,
+}
+
+/// Returns the `i`th Account ID of the given shape.
+pub fn account_id(shape: Shape, i: u32) -> String {
+    match shape {
+        Shape::Short => format!("a{}.near", i),
+        Shape::Nested => format!("app{}.alice-{}.testnet", i, i % 97),
+        Shape::Underscored => format!("sub_{}.app-{}.near", i % 1009, i),
+        Shape::NearImplicit => format!("{:064x}", u128::from(i) * 0x9e37_79b9_7f4a_7c15),
+        Shape::EthImplicit => format!("0x{:040x}", u128::from(i) * 0x2545_f491_4f6c_dd1d),
+        Shape::Invalid => format!("app{}..near", i),
+    }
+}
+
+/// `len` Account IDs, cycling through `shapes`.
+pub fn corpus(len: u32, shapes: &[Shape]) -> Vec<String> {
+    (0..len)
+        .map(|i| account_id(shapes[i as usize % shapes.len()], i))
+        .collect()
+}
@@ -0,0 +1,56 @@
+//! Parsing throughput of owned Account IDs.
+//!
+//! Run it with and without the `inline` feature to compare the two storages:
+//!
+//! ```text
+//! cargo bench --bench parse
+//! cargo bench --bench parse --features inline
+//! ```
+//!
+//! Both benchmarks make one heap allocation per Account ID with the default storage, and none with
+//! the `inline` one, so the difference between the two runs is the cost of those allocations.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use unc_account_id::{AccountId, AccountIdRef};
+
+mod common;
+use common::Shape;
+
+fn bench_parse(c: &mut Criterion) {
+    // 100k valid Account IDs of mixed lengths
+    let corpus = common::corpus(
+        100_000,
+        &[
+            Shape::Short,
+            Shape::Nested,
+            Shape::NearImplicit,
+            Shape::Underscored,
+        ],
+    );
+    let storage = if cfg!(feature = "inline") {
+        "inline"
+    } else {
+        "boxed"
+    };
+
+    let mut group = c.benchmark_group(format!("parse_100k_{}", storage));
+    group.throughput(Throughput::Elements(corpus.len() as u64));
+    group.bench_function("parse", |b| {
+        b.iter(|| {
+            for account_id in &corpus {
+                black_box(black_box(account_id.as_str()).parse::<AccountId>().unwrap());
+            }
+        })
+    });
+    group.bench_function("to_owned", |b| {
+        b.iter(|| {
+            for account_id in &corpus {
+                black_box(AccountIdRef::new(black_box(account_id)).unwrap().to_owned());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
@@ -0,0 +1,73 @@
+//! Validation throughput, over a mixed corpus and over each shape of Account ID on its own.
+//!
+//! The character-by-character implementation `validate` replaced is kept as the reference in the
+//! differential tests of `validation.rs`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use unc_account_id::{AccountId, ParseAccountError};
+
+mod common;
+use common::Shape;
+
+fn bench_validate(c: &mut Criterion) {
+    // 1M Account IDs of mixed lengths, one in eight of them invalid
+    let corpus = common::corpus(
+        1_000_000,
+        &[
+            Shape::Short,
+            Shape::Short,
+            Shape::Nested,
+            Shape::Nested,
+            Shape::NearImplicit,
+            Shape::EthImplicit,
+            Shape::Underscored,
+            Shape::Invalid,
+        ],
+    );
+
+    let mut group = c.benchmark_group("validate_1m");
+    group.bench_function("validate", |b| {
+        b.iter(|| {
+            for account_id in black_box(&corpus) {
+                let _: Result<(), ParseAccountError> = black_box(AccountId::validate(account_id));
+            }
+        })
+    });
+    group.finish();
+}
+
+/// Each shape of Account ID on its own, 10k times over.
+fn bench_shapes(c: &mut Criterion) {
+    let shapes = [
+        (
+            "valid_long",
+            "app-0123456789.sub_account.alice-and-bob.example-dao.testnet",
+        ),
+        (
+            "invalid_early",
+            "Alice.near-with-a-long-tail-of-labels.testnet",
+        ),
+        (
+            "near_implicit",
+            "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+        ),
+        ("eth_implicit", "0xb794f5ea0ba39494ce839613fffba74279579268"),
+    ];
+
+    let mut group = c.benchmark_group("validate_shapes_10k");
+    for (name, account_id) in shapes {
+        let corpus = vec![account_id; 10_000];
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for account_id in black_box(&corpus) {
+                    let _: Result<(), ParseAccountError> =
+                        black_box(AccountId::validate(account_id));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_validate, bench_shapes);
+criterion_main!(benches);
@@ -0,0 +1,87 @@
+//! Round-trips Account IDs through real databases with the `sqlx` integration.
+
+use unc_account_id::{AccountId, ParseAccountError, ParseErrorKind};
+
+#[sqlx::test]
+async fn test_sqlite(pool: sqlx::SqlitePool) {
+    sqlx::query("CREATE TABLE accounts (id INTEGER PRIMARY KEY, account_id TEXT NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let alice: AccountId = "alice.near".parse().unwrap();
+    sqlx::query("INSERT INTO accounts (id, account_id) VALUES (1, ?)")
+        .bind(&alice)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO accounts (id, account_id) VALUES (2, ?)")
+        .bind(AccountId::try_from("bob.near".to_string()).unwrap())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let selected: Vec<AccountId> =
+        sqlx::query_scalar("SELECT account_id FROM accounts ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(selected, ["alice.near", "bob.near"]);
+
+    let (account_id,): (AccountId,) =
+        sqlx::query_as("SELECT account_id FROM accounts WHERE account_id = ?")
+            .bind(&alice)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(account_id, alice);
+
+    // Malformed stored values fail to decode
+    sqlx::query("INSERT INTO accounts (id, account_id) VALUES (3, 'Alice.near')")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let err = sqlx::query_scalar::<_, AccountId>("SELECT account_id FROM accounts WHERE id = 3")
+        .fetch_one(&pool)
+        .await
+        .unwrap_err();
+    match err {
+        sqlx::Error::ColumnDecode { source, .. } => {
+            let err = source.downcast_ref::<ParseAccountError>().unwrap();
+            assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+        }
+        err => panic!("unexpected error: {}", err),
+    }
+}
+
+#[sqlx::test]
+#[ignore = "needs a Postgres database at DATABASE_URL"]
+async fn test_postgres(pool: sqlx::PgPool) {
+    sqlx::query("CREATE TABLE accounts (id INTEGER PRIMARY KEY, account_id TEXT NOT NULL)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let alice: AccountId = "alice.near".parse().unwrap();
+    sqlx::query("INSERT INTO accounts (id, account_id) VALUES (1, $1), (2, 'Alice.near')")
+        .bind(&alice)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let account_id: AccountId =
+        sqlx::query_scalar("SELECT account_id FROM accounts WHERE account_id = $1")
+            .bind(&alice)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(account_id, alice);
+
+    let err = sqlx::query_scalar::<_, AccountId>("SELECT account_id FROM accounts WHERE id = 2")
+        .fetch_one(&pool)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, sqlx::Error::ColumnDecode { source, .. } if source.is::<ParseAccountError>())
+    );
+}
@@ -1,6 +1,14 @@
-use std::{borrow::Cow, fmt, ops::Deref, str::FromStr};
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::String,
+    vec::Vec,
+};
+use core::{fmt, ops::Deref, str::FromStr};
 
-use crate::{AccountIdRef, ParseAccountError};
+use crate::{AccountIdRef, ParseAccountError, ParseErrorKind};
 
 /// NEAR Account Identifier.
 ///
@@ -20,15 +28,33 @@ use crate::{AccountIdRef, ParseAccountError};
 /// assert!("ƒelicia.near".parse::<AccountId>().is_err()); // (ƒ is not f)
 /// ```
 #[derive(Eq, Ord, Hash, Clone, Debug, PartialEq, PartialOrd)]
-#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "abi", derive(borsh::BorshSchema))]
-pub struct AccountId(pub(crate) Box<str>);
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Text)
+)]
+pub struct AccountId(pub(crate) Repr);
+
+/// The storage of an [`AccountId`]: a boxed `str`, or an inline buffer with the `inline` feature.
+#[cfg(not(feature = "inline"))]
+pub(crate) type Repr = Box<str>;
+#[cfg(feature = "inline")]
+pub(crate) type Repr = crate::inline::InlineStr;
+
+// A pointer and a length: no capacity, as Account IDs never grow.
+#[cfg(not(feature = "inline"))]
+const _: () = assert!(core::mem::size_of::<AccountId>() == 2 * core::mem::size_of::<usize>());
 
 impl AccountId {
     /// Shortest valid length for a NEAR Account ID.
     pub const MIN_LEN: usize = crate::validation::MIN_LEN;
     /// Longest valid length for a NEAR Account ID.
     pub const MAX_LEN: usize = crate::validation::MAX_LEN;
+    /// Shortest length of a top-level Account ID that anyone can create.
+    ///
+    /// See [`AccountIdRef::REGISTRAR_ACCOUNT_MIN_LEN`].
+    pub const REGISTRAR_ACCOUNT_MIN_LEN: usize = AccountIdRef::REGISTRAR_ACCOUNT_MIN_LEN;
 
     /// Creates an `AccountId` without any validation checks.
     ///
@@ -41,6 +67,20 @@ impl AccountId {
     /// the caller bears the responsibility of ensuring that the Account ID is valid.
     /// You can use the [`AccountId::validate`] function sometime after its creation but before it's use.
     ///
+    /// An invalid `AccountId` never leads to memory unsafety, but every method relying on the
+    /// Account ID rules gives meaningless results for it: [`is_top_level`](AccountIdRef::is_top_level),
+    /// [`is_sub_account_of`](AccountIdRef::is_sub_account_of), [`parent`](AccountIdRef::parent),
+    /// [`ancestors`](AccountIdRef::ancestors), [`depth`](AccountIdRef::depth) and
+    /// [`get_account_type`](AccountIdRef::get_account_type) among others.
+    ///
+    /// With the `validate-unchecked` feature enabled, the Account ID is validated anyway, exactly like
+    /// [`AccountId::validate`] does.
+    ///
+    /// ## Panics
+    ///
+    /// With the `validate-unchecked` feature enabled, panics if the Account ID is invalid.
+    /// With the `inline` feature enabled, panics if it is longer than [`AccountId::MAX_LEN`].
+    ///
     /// ## Examples
     ///
     /// ```
@@ -53,7 +93,52 @@ impl AccountId {
     #[cfg(feature = "internal_unstable")]
     #[deprecated = "AccountId construction without validation is illegal since nearcore#4440"]
     pub fn new_unvalidated(account_id: String) -> Self {
-        Self(account_id.into_boxed_str())
+        #[cfg(feature = "validate-unchecked")]
+        if let Err(err) = crate::validation::validate(&account_id) {
+            panic!("invalid Account ID {:?}: {}", account_id, err);
+        }
+        Self(account_id.into())
+    }
+
+    /// Construct an [`AccountId`] from a boxed string without validating it, reusing the allocation.
+    ///
+    /// This is [`AccountId::new_unvalidated`] for `Box<str>`, with the same caveats.
+    ///
+    /// ## Safety
+    ///
+    /// See [`AccountId::new_unvalidated`].
+    ///
+    /// ## Panics
+    ///
+    /// With the `validate-unchecked` feature enabled, panics if the Account ID is invalid.
+    /// With the `inline` feature enabled, panics if it is longer than [`AccountId::MAX_LEN`].
+    #[doc(hidden)]
+    #[cfg(feature = "internal_unstable")]
+    #[deprecated = "AccountId construction without validation is illegal since nearcore#4440"]
+    pub fn from_boxed_unchecked(account_id: Box<str>) -> Self {
+        #[cfg(feature = "validate-unchecked")]
+        if let Err(err) = crate::validation::validate(&account_id) {
+            panic!("invalid Account ID {:?}: {}", account_id, err);
+        }
+        Self(String::from(account_id).into())
+    }
+
+    /// Converts the `AccountId` into a boxed string, which holds no spare capacity.
+    ///
+    /// The allocation is handed over as is, unless the `inline` feature is enabled,
+    /// in which case the bytes are copied onto the heap.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountId;
+    ///
+    /// let alice: AccountId = "alice.near".parse().unwrap();
+    /// let boxed: Box<str> = alice.into_boxed_str();
+    /// assert_eq!(&*boxed, "alice.near");
+    /// ```
+    pub fn into_boxed_str(self) -> Box<str> {
+        String::from(self.0).into_boxed_str()
     }
 
     /// Validates a string as a well-structured NEAR Account ID.
@@ -115,6 +200,370 @@ impl AccountId {
     pub fn validate(account_id: &str) -> Result<(), ParseAccountError> {
         crate::validation::validate(account_id)
     }
+
+    /// Validates every Account ID in a map, e.g. the account fields of a config file.
+    ///
+    /// `extract` returns the Account ID held by each value. Rather than stopping at the first
+    /// invalid Account ID, this returns the key and error of every failure, in key order.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    /// use unc_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let config: BTreeMap<String, &str> = [
+    ///     ("owner".to_string(), "alice.near"),
+    ///     ("treasury".to_string(), "Treasury.near"),
+    ///     ("oracle".to_string(), "oracle..near"),
+    /// ]
+    /// .into_iter()
+    /// .collect();
+    ///
+    /// let errors = AccountId::validate_map(&config, |v| v).unwrap_err();
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(errors[0].0, "oracle");
+    /// assert_eq!(errors[0].1.kind(), &ParseErrorKind::RedundantSeparator);
+    /// assert_eq!(errors[1].0, "treasury");
+    /// assert_eq!(errors[1].1.kind(), &ParseErrorKind::InvalidChar);
+    /// ```
+    pub fn validate_map<V>(
+        map: &BTreeMap<String, V>,
+        extract: impl Fn(&V) -> &str,
+    ) -> Result<(), Vec<(String, ParseAccountError)>> {
+        let errors: Vec<_> = map
+            .iter()
+            .filter_map(|(key, value)| {
+                crate::validation::validate(extract(value))
+                    .err()
+                    .map(|err| (key.clone(), err))
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Validates a `String` and turns it into an `AccountId`, handing the `String` back on failure.
+    ///
+    /// This works like `AccountId::try_from(String)`, except that an invalid input
+    /// isn't lost, so that it can still be shown to the user. On success the buffer is moved into the `AccountId`,
+    /// which only reallocates to drop spare capacity.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let alice = AccountId::validate_owned("alice.near".to_string()).unwrap();
+    /// assert_eq!(alice, "alice.near");
+    ///
+    /// let (input, err) = AccountId::validate_owned("Alice.near".to_string()).unwrap_err();
+    /// assert_eq!(input, "Alice.near");
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+    /// ```
+    pub fn validate_owned(account_id: String) -> Result<Self, (String, ParseAccountError)> {
+        match crate::validation::validate(&account_id) {
+            Ok(()) => Ok(Self(account_id.into())),
+            Err(err) => Err((account_id, err)),
+        }
+    }
+
+    /// Joins labels with `.` into an `AccountId`.
+    ///
+    /// Labels are given leaf first, in the same order as they are displayed and as
+    /// [`AccountIdRef::labels`] yields them. This is the same as [`from_parts`](AccountId::from_parts),
+    /// with the same errors.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let app = AccountId::from_labels(["app", "alice", "near"]).unwrap();
+    /// assert_eq!(app, "app.alice.near");
+    ///
+    /// let err = AccountId::from_labels(Vec::<String>::new()).unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::TooShort);
+    ///
+    /// let err = AccountId::from_labels(["app", "b..c", "near"]).unwrap_err();
+    /// assert_eq!(err, AccountId::from_parts(["app", "b..c", "near"]).unwrap_err());
+    /// ```
+    pub fn from_labels<I, S>(labels: I) -> Result<Self, ParseAccountError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::from_parts(labels)
+    }
+
+    /// Decodes an `AccountId` from a token produced by [`AccountIdRef::to_url_token`].
+    ///
+    /// Returns [`ParseErrorKind::InvalidChar`](crate::ParseErrorKind::InvalidChar) pointing into the token
+    /// if it is not valid base32, otherwise the decoded bytes are validated like [`AccountId::from_utf8`] does,
+    /// with indices pointing into them.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let alice = AccountId::from_url_token("mfwgsy3ffzxgkyls").unwrap();
+    /// assert_eq!(alice, "alice.near");
+    ///
+    /// assert!(
+    ///   matches!(
+    ///     AccountId::from_url_token("mfwgsy3ffzxgkyl!"),
+    ///     Err(err) if err.kind() == &ParseErrorKind::InvalidChar
+    ///   )
+    /// );
+    /// ```
+    pub fn from_url_token(token: &str) -> Result<Self, ParseAccountError> {
+        let bytes = crate::base32::decode(token).map_err(|char| ParseAccountError {
+            kind: ParseErrorKind::InvalidChar,
+            char: Some(char),
+            len: None,
+        })?;
+        Self::from_utf8(bytes)
+    }
+
+    /// Assigns `key` to one of `shard_count` shards under `base`, returning `shard-{idx}.{base}`.
+    ///
+    /// The shard index is the 64-bit FNV-1a hash of `key` modulo `shard_count`. The hash is
+    /// part of the contract of this function, so the same key always lands on the same shard,
+    /// across platforms and releases.
+    ///
+    /// Fails if the resulting Account ID is invalid, e.g. if `base` leaves no room for the prefix.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `shard_count` is zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountId, AccountIdRef};
+    ///
+    /// let base = AccountIdRef::new_or_panic("store.near");
+    ///
+    /// let shard = AccountId::shard_subaccount(base, b"alice", 16).unwrap();
+    /// assert_eq!(shard, "shard-7.store.near");
+    /// assert!(shard.is_sub_account_of(base));
+    /// ```
+    pub fn shard_subaccount(
+        base: &AccountIdRef,
+        key: &[u8],
+        shard_count: u32,
+    ) -> Result<Self, ParseAccountError> {
+        assert!(shard_count > 0, "`shard_count` must be non-zero");
+
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0100_0000_01b3;
+        let hash = key.iter().fold(FNV_OFFSET_BASIS, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+        });
+        let idx = hash % u64::from(shard_count);
+
+        Self::try_from(format!("shard-{}.{}", idx, base))
+    }
+
+    /// Returns the longest account that all of `accounts` are equal to or descend from.
+    ///
+    /// This folds [`AccountIdRef::shared_suffix`] over the accounts, so a single account is its own
+    /// common ancestor. Returns `None` if there are no accounts, or if they don't all share a top-level account.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountId, AccountIdRef};
+    ///
+    /// let accounts = ["x.app.near", "y.app.near", "z.y.app.near"].map(AccountIdRef::new_or_panic);
+    /// assert_eq!(AccountId::common_ancestor_of(accounts).unwrap(), "app.near");
+    ///
+    /// let accounts = ["app.near", "app.testnet"].map(AccountIdRef::new_or_panic);
+    /// assert_eq!(AccountId::common_ancestor_of(accounts), None);
+    /// ```
+    pub fn common_ancestor_of<'a>(
+        accounts: impl IntoIterator<Item = &'a AccountIdRef>,
+    ) -> Option<&'a AccountIdRef> {
+        let mut accounts = accounts.into_iter();
+        let first = accounts.next()?;
+        accounts.try_fold(first, |ancestor, account| ancestor.shared_suffix(account))
+    }
+
+    /// Builds an `AccountId` from its labels, joining them with `.`, e.g. `["app", "alice", "near"]`.
+    ///
+    /// Each part is validated as a label on its own, like [`validate_new_label`](crate::validate_new_label) does,
+    /// so a part containing a `.` is rejected. The Account ID is written out with a single allocation of the
+    /// exact length. The errors are, in order of precedence:
+    ///
+    /// - [`ParseErrorKind::TooLong`] if the Account ID would be longer than [`AccountId::MAX_LEN`].
+    /// - The [`validate_segment`](crate::validate_segment) error of the first invalid part,
+    ///   so [`ParseErrorKind::TooShort`] with a length of 0 for an empty part.
+    /// - [`ParseErrorKind::TooShort`] if the Account ID is shorter than [`AccountId::MIN_LEN`],
+    ///   including having no parts at all.
+    ///
+    /// Like with [`AccountIdBuilder`](crate::AccountIdBuilder), indices are positions in the resulting Account ID.
+    /// This is the inverse of [`labels`](AccountIdRef::labels).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let app = AccountId::from_parts(["app", "alice", "near"]).unwrap();
+    /// assert_eq!(app, "app.alice.near");
+    /// assert_eq!(AccountId::from_parts(app.labels()).unwrap(), app);
+    ///
+    /// let err = AccountId::from_parts(["app", "Alice", "near"]).unwrap_err();
+    /// assert_eq!(err.to_string(), "the Account ID contains an invalid character 'A' at index 4");
+    ///
+    /// let err = AccountId::from_parts(["app", "alice.near"]).unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+    /// ```
+    pub fn from_parts<I>(parts: I) -> Result<Self, ParseAccountError>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        crate::validation::join_labels(parts, None)
+    }
+
+    /// Validates raw bytes and turns them into an `AccountId`, reusing the buffer.
+    ///
+    /// Since Account IDs are ASCII-only, this needs no separate UTF-8 check: bytes that aren't
+    /// valid UTF-8 are reported as [`ParseErrorKind::InvalidUtf8`] at their byte offset.
+    /// On success the buffer is moved into the `AccountId`, which only reallocates to drop spare capacity.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let alice = AccountId::from_utf8(b"alice.near".to_vec()).unwrap();
+    /// assert_eq!(alice, "alice.near");
+    ///
+    /// let err = AccountId::from_utf8(b"alice\xff.near".to_vec()).unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidUtf8);
+    /// assert_eq!(err.to_string(), "the Account ID is not valid UTF-8 at index 5");
+    /// ```
+    pub fn from_utf8(bytes: Vec<u8>) -> Result<Self, ParseAccountError> {
+        crate::validation::validate_bytes(&bytes)?;
+        // SAFETY: `validate_bytes` only accepts ASCII, which is valid UTF-8.
+        let account_id = unsafe { String::from_utf8_unchecked(bytes) };
+        Ok(Self(account_id.into()))
+    }
+
+    /// Validates raw bytes and turns them into an `AccountId`, reusing the buffer.
+    ///
+    /// This is an alias for [`AccountId::from_utf8`], named after the fallible conversions of `std`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let alice = AccountId::try_from_utf8(b"alice.near".to_vec()).unwrap();
+    /// assert_eq!(alice, "alice.near");
+    ///
+    /// let err = AccountId::try_from_utf8(b"\xc3\x28near".to_vec()).unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidUtf8);
+    /// ```
+    pub fn try_from_utf8(bytes: Vec<u8>) -> Result<Self, ParseAccountError> {
+        Self::from_utf8(bytes)
+    }
+
+    /// Decodes an `AccountId` encoded by [`AccountIdRef::to_bytes`]: a single length byte
+    /// followed by the bytes of the Account ID.
+    ///
+    /// The input must hold exactly one encoded Account ID. If the length prefix is missing or
+    /// doesn't match the number of bytes that follow, the error is
+    /// [`ParseErrorKind::InvalidLengthPrefix`]. Otherwise the bytes are validated like
+    /// [`AccountId::from_utf8`] does.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let alice = AccountId::from_bytes(b"\x0aalice.near").unwrap();
+    /// assert_eq!(alice, "alice.near");
+    ///
+    /// let err = AccountId::from_bytes(b"\x0aalice").unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidLengthPrefix);
+    ///
+    /// let err = AccountId::from_bytes(b"\x0aAlice.near").unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseAccountError> {
+        match bytes.split_first() {
+            Some((&len, account_id)) if usize::from(len) == account_id.len() => {
+                AccountIdRef::from_utf8(account_id).map(ToOwned::to_owned)
+            }
+            _ => Err(ParseAccountError {
+                kind: ParseErrorKind::InvalidLengthPrefix,
+                char: None,
+                len: None,
+            }),
+        }
+    }
+
+    /// Parses an `AccountId`, turning ASCII upper-case letters into lower-case first.
+    ///
+    /// See [`AccountIdRef::new_normalized`], which only allocates when there is something to lowercase.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let alice = AccountId::new_normalized("ALICE.NEAR").unwrap();
+    /// assert_eq!(alice, "alice.near");
+    ///
+    /// // Only ASCII is lowercased: this is a Cyrillic `А`
+    /// let err = AccountId::new_normalized("Аlice.near").unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+    /// ```
+    pub fn new_normalized<S: AsRef<str>>(account_id: S) -> Result<Self, ParseAccountError> {
+        AccountIdRef::new_normalized(account_id.as_ref()).map(Cow::into_owned)
+    }
+
+    /// Parses an `AccountId` pasted by a user, trimming surrounding whitespace and turning ASCII
+    /// upper-case letters into lower-case first.
+    ///
+    /// Valid Account IDs are returned unchanged, and anything else wrong with the input is still
+    /// rejected. This is [`AccountId::new_normalized`] on the trimmed input, meant for user input:
+    /// strict parsing with [`FromStr`] keeps rejecting `" Alice.near"`.
+    ///
+    /// Errors point at positions in the original `input`, while length errors report the length
+    /// of the trimmed Account ID.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountId, ParseErrorKind};
+    ///
+    /// let alice = AccountId::normalize("  Alice.NEAR  ").unwrap();
+    /// assert_eq!(alice, "alice.near");
+    /// assert!("  Alice.NEAR  ".parse::<AccountId>().is_err());
+    ///
+    /// let err = AccountId::normalize(" Alice Near").unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+    /// assert_eq!(err.position(), Some(6));
+    /// ```
+    pub fn normalize(input: &str) -> Result<Self, ParseAccountError> {
+        let trimmed = input.trim_start();
+        let offset = input.len() - trimmed.len();
+        Self::new_normalized(trimmed.trim_end()).map_err(|mut err| {
+            if let Some((idx, _)) = &mut err.char {
+                *idx += offset;
+            }
+            err
+        })
+    }
 }
 
 impl AsRef<str> for AccountId {
@@ -129,6 +578,12 @@ impl AsRef<AccountIdRef> for AccountId {
     }
 }
 
+impl AsRef<[u8]> for AccountId {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
 impl Deref for AccountId {
     type Target = AccountIdRef;
 
@@ -137,12 +592,20 @@ impl Deref for AccountId {
     }
 }
 
-impl std::borrow::Borrow<AccountIdRef> for AccountId {
+impl core::borrow::Borrow<AccountIdRef> for AccountId {
     fn borrow(&self) -> &AccountIdRef {
         AccountIdRef::new_unvalidated(self)
     }
 }
 
+// `Hash`, `Eq` and `Ord` of `AccountId` and `AccountIdRef` are derived from the inner string,
+// so they agree with those of `str` as `Borrow` requires.
+impl core::borrow::Borrow<str> for AccountId {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
 impl FromStr for AccountId {
     type Err = ParseAccountError;
 
@@ -157,34 +620,65 @@ impl TryFrom<Box<str>> for AccountId {
 
     fn try_from(account_id: Box<str>) -> Result<Self, Self::Error> {
         crate::validation::validate(&account_id)?;
-        Ok(Self(account_id))
+        Ok(Self(String::from(account_id).into()))
     }
 }
 
+/// The `String` is moved into the `AccountId`, which only reallocates to drop spare capacity.
+///
+/// See [`AccountId::validate_owned`] to get the `String` back on failure.
 impl TryFrom<String> for AccountId {
     type Error = ParseAccountError;
 
     fn try_from(account_id: String) -> Result<Self, Self::Error> {
         crate::validation::validate(&account_id)?;
-        Ok(Self(account_id.into_boxed_str()))
+        Ok(Self(account_id.into()))
+    }
+}
+
+/// Owned strings are moved into the `AccountId`, and only borrowed ones are copied.
+impl TryFrom<Cow<'_, str>> for AccountId {
+    type Error = ParseAccountError;
+
+    fn try_from(account_id: Cow<'_, str>) -> Result<Self, Self::Error> {
+        match account_id {
+            Cow::Borrowed(account_id) => AccountIdRef::new(account_id).map(ToOwned::to_owned),
+            Cow::Owned(account_id) => Self::try_from(account_id),
+        }
+    }
+}
+
+impl TryFrom<Vec<u8>> for AccountId {
+    type Error = ParseAccountError;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::from_utf8(bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for AccountId {
+    type Error = ParseAccountError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        crate::validation::validate_bytes(bytes).map(|account_id| Self(account_id.into()))
     }
 }
 
 impl fmt::Display for AccountId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Display::fmt(&self.0, f)
+        fmt::Display::fmt(self.as_str(), f)
     }
 }
 
 impl From<AccountId> for String {
     fn from(account_id: AccountId) -> Self {
-        account_id.0.into_string()
+        account_id.0.into()
     }
 }
 
 impl From<AccountId> for Box<str> {
     fn from(value: AccountId) -> Box<str> {
-        value.0
+        value.into_boxed_str()
     }
 }
 
@@ -200,7 +694,7 @@ impl PartialEq<AccountIdRef> for AccountId {
     }
 }
 
-impl<'a> PartialEq<AccountId> for &'a AccountIdRef {
+impl PartialEq<AccountId> for &AccountIdRef {
     fn eq(&self, other: &AccountId) -> bool {
         &self.0 == other.as_str()
     }
@@ -236,7 +730,7 @@ impl PartialEq<str> for AccountId {
     }
 }
 
-impl<'a> PartialEq<AccountId> for &'a str {
+impl PartialEq<AccountId> for &str {
     fn eq(&self, other: &AccountId) -> bool {
         *self == other.as_str()
     }
@@ -249,65 +743,155 @@ impl<'a> PartialEq<&'a str> for AccountId {
 }
 
 impl PartialOrd<AccountId> for AccountIdRef {
-    fn partial_cmp(&self, other: &AccountId) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
         self.0.partial_cmp(other.as_str())
     }
 }
 
 impl PartialOrd<AccountIdRef> for AccountId {
-    fn partial_cmp(&self, other: &AccountIdRef) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &AccountIdRef) -> Option<core::cmp::Ordering> {
         self.as_str().partial_cmp(&other.0)
     }
 }
 
-impl<'a> PartialOrd<AccountId> for &'a AccountIdRef {
-    fn partial_cmp(&self, other: &AccountId) -> Option<std::cmp::Ordering> {
+impl PartialOrd<AccountId> for &AccountIdRef {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
         self.0.partial_cmp(other.as_str())
     }
 }
 
 impl<'a> PartialOrd<&'a AccountIdRef> for AccountId {
-    fn partial_cmp(&self, other: &&'a AccountIdRef) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &&'a AccountIdRef) -> Option<core::cmp::Ordering> {
         self.as_str().partial_cmp(&other.0)
     }
 }
 
 impl PartialOrd<AccountId> for String {
-    fn partial_cmp(&self, other: &AccountId) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
         self.as_str().partial_cmp(other.as_str())
     }
 }
 
 impl PartialOrd<String> for AccountId {
-    fn partial_cmp(&self, other: &String) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &String) -> Option<core::cmp::Ordering> {
         self.as_str().partial_cmp(other.as_str())
     }
 }
 
 impl PartialOrd<AccountId> for str {
-    fn partial_cmp(&self, other: &AccountId) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
         self.partial_cmp(other.as_str())
     }
 }
 
 impl PartialOrd<str> for AccountId {
-    fn partial_cmp(&self, other: &str) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &str) -> Option<core::cmp::Ordering> {
         self.as_str().partial_cmp(other)
     }
 }
 
-impl<'a> PartialOrd<AccountId> for &'a str {
-    fn partial_cmp(&self, other: &AccountId) -> Option<std::cmp::Ordering> {
+impl PartialOrd<AccountId> for &str {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
         self.partial_cmp(&other.as_str())
     }
 }
 
 impl<'a> PartialOrd<&'a str> for AccountId {
-    fn partial_cmp(&self, other: &&'a str) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &&'a str) -> Option<core::cmp::Ordering> {
         self.as_str().partial_cmp(*other)
     }
 }
 
+impl PartialEq<[u8]> for AccountId {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_bytes() == other
+    }
+}
+
+impl PartialEq<AccountId> for [u8] {
+    fn eq(&self, other: &AccountId) -> bool {
+        self == other.as_bytes()
+    }
+}
+
+impl<'a> PartialEq<&'a [u8]> for AccountId {
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        self.as_bytes() == *other
+    }
+}
+
+impl PartialEq<AccountId> for &[u8] {
+    fn eq(&self, other: &AccountId) -> bool {
+        *self == other.as_bytes()
+    }
+}
+
+impl PartialOrd<[u8]> for AccountId {
+    fn partial_cmp(&self, other: &[u8]) -> Option<core::cmp::Ordering> {
+        self.as_bytes().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<AccountId> for [u8] {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(other.as_bytes())
+    }
+}
+
+impl<'a> PartialOrd<&'a [u8]> for AccountId {
+    fn partial_cmp(&self, other: &&'a [u8]) -> Option<core::cmp::Ordering> {
+        self.as_bytes().partial_cmp(*other)
+    }
+}
+
+impl PartialOrd<AccountId> for &[u8] {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
+        (*self).partial_cmp(other.as_bytes())
+    }
+}
+
+impl<'a> PartialEq<AccountId> for Cow<'a, AccountIdRef> {
+    fn eq(&self, other: &AccountId) -> bool {
+        **self == **other
+    }
+}
+
+impl<'a> PartialEq<Cow<'a, AccountIdRef>> for AccountId {
+    fn eq(&self, other: &Cow<'a, AccountIdRef>) -> bool {
+        **self == **other
+    }
+}
+
+impl<'a> PartialOrd<AccountId> for Cow<'a, AccountIdRef> {
+    fn partial_cmp(&self, other: &AccountId) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<'a> PartialOrd<Cow<'a, AccountIdRef>> for AccountId {
+    fn partial_cmp(&self, other: &Cow<'a, AccountIdRef>) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl From<AccountId> for Box<AccountIdRef> {
+    fn from(value: AccountId) -> Box<AccountIdRef> {
+        // Safety: `AccountIdRef` is a `#[repr(transparent)]` newtype around `str`,
+        // so the allocation can be reinterpreted as is.
+        let account_id = String::from(value.0).into_boxed_str();
+        unsafe { Box::from_raw(Box::into_raw(account_id) as *mut AccountIdRef) }
+    }
+}
+
+impl From<Box<AccountIdRef>> for AccountId {
+    fn from(value: Box<AccountIdRef>) -> Self {
+        // Safety: `AccountIdRef` is a `#[repr(transparent)]` newtype around `str`,
+        // so the allocation can be reinterpreted as is.
+        let account_id = unsafe { Box::from_raw(Box::into_raw(value) as *mut str) };
+        Self(String::from(account_id).into())
+    }
+}
+
 impl<'a> From<AccountId> for Cow<'a, AccountIdRef> {
     fn from(value: AccountId) -> Self {
         Cow::Owned(value)
@@ -346,6 +930,929 @@ mod tests {
     #[allow(unused_imports)]
     use super::*;
 
+    #[test]
+    fn test_validate_map() {
+        struct Field {
+            account_id: &'static str,
+        }
+
+        let valid = [
+            ("owner", "alice.near"),
+            ("treasury", "treasury.alice.near"),
+            ("oracle", "0xb794f5ea0ba39494ce839613fffba74279579268"),
+        ];
+        let mut config: BTreeMap<String, Field> = valid
+            .iter()
+            .map(|&(key, account_id)| (key.to_string(), Field { account_id }))
+            .collect();
+        assert_eq!(AccountId::validate_map(&config, |f| f.account_id), Ok(()));
+
+        config.insert("admin".to_string(), Field { account_id: "a" });
+        config.insert(
+            "relayer".to_string(),
+            Field {
+                account_id: "relayer_.near",
+            },
+        );
+        assert_eq!(
+            AccountId::validate_map(&config, |f| f.account_id),
+            Err(vec![
+                ("admin".to_string(), ParseAccountError::too_short(1)),
+                (
+                    "relayer".to_string(),
+                    ParseAccountError {
+                        kind: ParseErrorKind::RedundantSeparator,
+                        char: Some((8, '.')),
+                        len: None,
+                    }
+                ),
+            ])
+        );
+
+        assert_eq!(
+            AccountId::validate_map(&BTreeMap::<String, String>::new(), String::as_str),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_from_parts() {
+        let from_parts = |parts: &[&str]| AccountId::from_parts(parts);
+
+        assert_eq!(from_parts(&["near"]).unwrap(), "near");
+        assert_eq!(from_parts(&["alice", "near"]).unwrap(), "alice.near");
+        assert_eq!(
+            AccountId::from_parts(vec![
+                String::from("app"),
+                String::from("alice"),
+                "near".into()
+            ])
+            .unwrap(),
+            "app.alice.near"
+        );
+        let longest = "a".repeat(59);
+        assert_eq!(
+            from_parts(&[&longest, "near"]).unwrap().len(),
+            AccountId::MAX_LEN
+        );
+
+        let invalid_char = |idx, c| ParseAccountError {
+            kind: ParseErrorKind::InvalidChar,
+            char: Some((idx, c)),
+            len: None,
+        };
+        let redundant_separator = |idx, c| ParseAccountError {
+            kind: ParseErrorKind::RedundantSeparator,
+            char: Some((idx, c)),
+            len: None,
+        };
+        let cases: &[(&[&str], ParseAccountError)] = &[
+            (&[], ParseAccountError::too_short(0)),
+            (&[""], ParseAccountError::too_short(0)),
+            (&["a"], ParseAccountError::too_short(1)),
+            (&["app", "Alice", "near"], invalid_char(4, 'A')),
+            (&["app", "alice.near"], invalid_char(9, '.')),
+            (&["app", "alice", "ne@r"], invalid_char(12, '@')),
+            (&["app", "-alice", "near"], redundant_separator(4, '-')),
+            (&["app", "alice_", "near"], redundant_separator(9, '_')),
+            // Empty parts are too short labels
+            (&["", "near"], ParseAccountError::too_short(0)),
+            (&["alice", "", "near"], ParseAccountError::too_short(0)),
+            (&["alice", "near", ""], ParseAccountError::too_short(0)),
+            (&["a", "b..c", "near"], invalid_char(3, '.')),
+            // The first invalid part is reported
+            (&["app", "Alice", "N"], invalid_char(4, 'A')),
+            (
+                &[&longest, "near", "@"],
+                ParseAccountError::too_long(66, AccountId::MAX_LEN),
+            ),
+            (
+                &[&longest, "nearly"],
+                ParseAccountError::too_long(66, AccountId::MAX_LEN),
+            ),
+        ];
+        for (parts, expected) in cases {
+            assert_eq!(from_parts(parts).as_ref(), Err(expected), "{:?}", parts);
+        }
+
+        for (parts, expected) in cases {
+            assert_eq!(
+                AccountId::from_labels(*parts).as_ref(),
+                Err(expected),
+                "{:?}",
+                parts
+            );
+        }
+
+        // The same errors as validating the joined Account ID, as long as no part is empty or holds a `.`
+        for (parts, _) in cases.iter().filter(|(parts, _)| parts.len() > 1) {
+            if !parts
+                .iter()
+                .any(|part| part.is_empty() || part.contains('.'))
+            {
+                assert_eq!(
+                    from_parts(parts).unwrap_err().kind(),
+                    AccountId::validate(&parts.join(".")).unwrap_err().kind(),
+                    "{:?}",
+                    parts
+                );
+            }
+        }
+
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            assert_eq!(
+                AccountId::from_parts(account_id.labels()).unwrap(),
+                account_id
+            );
+        }
+        for account_id in crate::test_data::BAD_ACCOUNT_IDS {
+            let parts: Vec<_> = account_id.split('.').collect();
+            assert!(AccountId::from_parts(&parts).is_err(), "{:?}", account_id);
+        }
+    }
+
+    #[test]
+    fn test_into_boxed_str() {
+        // Spare capacity is dropped on construction, so the box holds exactly the Account ID
+        let mut input = String::with_capacity(64);
+        input.push_str("alice.near");
+        let account_id = AccountId::try_from(input).unwrap();
+        let ptr = account_id.as_str().as_ptr();
+
+        let boxed = account_id.into_boxed_str();
+        assert_eq!(&*boxed, "alice.near");
+        let string = String::from(boxed);
+        assert_eq!(string.capacity(), "alice.near".len());
+        if !cfg!(feature = "inline") {
+            assert_eq!(string.as_ptr(), ptr);
+        }
+
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            let parsed: AccountId = account_id.parse().unwrap();
+            assert_eq!(&*parsed.clone().into_boxed_str(), account_id);
+            assert_eq!(Box::<str>::from(parsed), (*account_id).into());
+        }
+    }
+
+    #[cfg(feature = "internal_unstable")]
+    #[test]
+    #[allow(deprecated)]
+    fn test_from_boxed_unchecked() {
+        let boxed: Box<str> = "alice.near".into();
+        let ptr = boxed.as_ptr();
+        let account_id = AccountId::from_boxed_unchecked(boxed);
+        assert_eq!(account_id, "alice.near");
+        if !cfg!(feature = "inline") {
+            assert_eq!(account_id.as_str().as_ptr(), ptr);
+        }
+        assert_eq!(
+            AccountId::from_boxed_unchecked(account_id.clone().into_boxed_str()),
+            account_id
+        );
+    }
+
+    #[test]
+    fn test_try_from_string() {
+        let input = String::from("alice.near");
+        let ptr = input.as_ptr();
+        let account_id = AccountId::try_from(input).unwrap();
+        assert_eq!(account_id, "alice.near");
+        // No spare capacity, so the buffer is moved without reallocating, unless stored inline
+        if !cfg!(feature = "inline") {
+            assert_eq!(account_id.as_str().as_ptr(), ptr);
+        }
+
+        let boxed: Box<str> = "bob.near".into();
+        let ptr = boxed.as_ptr();
+        let account_id = AccountId::try_from(boxed).unwrap();
+        assert_eq!(account_id, "bob.near");
+        if !cfg!(feature = "inline") {
+            assert_eq!(account_id.as_str().as_ptr(), ptr);
+        }
+
+        for account_id in crate::test_data::BAD_ACCOUNT_IDS {
+            assert_eq!(
+                AccountId::try_from(account_id.to_string()),
+                Err(AccountId::validate(account_id).unwrap_err())
+            );
+        }
+    }
+
+    #[test]
+    fn test_try_from_cow() {
+        let owned: Cow<'_, str> = Cow::Owned(String::from("alice.near"));
+        let ptr = owned.as_ptr();
+        let account_id = AccountId::try_from(owned).unwrap();
+        assert_eq!(account_id, "alice.near");
+        // No spare capacity, so the buffer is moved without reallocating, unless stored inline
+        if !cfg!(feature = "inline") {
+            assert_eq!(account_id.as_str().as_ptr(), ptr);
+        }
+
+        let input = String::from("bob.near");
+        let borrowed = Cow::Borrowed(input.as_str());
+        let account_id_ref = <&AccountIdRef>::try_from(&borrowed).unwrap();
+        assert_eq!(account_id_ref.as_str().as_ptr(), input.as_ptr());
+        let account_id = AccountId::try_from(borrowed).unwrap();
+        assert_eq!(account_id, "bob.near");
+
+        let owned: Cow<'_, str> = Cow::Owned(String::from("carol.near"));
+        let account_id_ref = <&AccountIdRef>::try_from(&owned).unwrap();
+        assert_eq!(account_id_ref.as_str().as_ptr(), owned.as_ptr());
+
+        for account_id in crate::test_data::BAD_ACCOUNT_IDS {
+            let err = AccountId::try_from(account_id.to_string()).unwrap_err();
+            assert_eq!(
+                AccountId::try_from(Cow::<str>::Owned(account_id.to_string())),
+                Err(err.clone())
+            );
+            assert_eq!(
+                AccountId::try_from(Cow::Borrowed(account_id)),
+                Err(err.clone())
+            );
+            assert_eq!(
+                <&AccountIdRef>::try_from(&Cow::Borrowed(account_id)),
+                Err(err)
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_owned() {
+        let input = String::from("alice.near");
+        let ptr = input.as_ptr();
+        let account_id = AccountId::validate_owned(input).unwrap();
+        assert_eq!(account_id, "alice.near");
+        // No spare capacity, so the buffer is reused, unless stored inline
+        if !cfg!(feature = "inline") {
+            assert_eq!(account_id.as_str().as_ptr(), ptr);
+        }
+
+        for account_id in crate::test_data::BAD_ACCOUNT_IDS {
+            let (input, err) = AccountId::validate_owned(account_id.to_string()).unwrap_err();
+            assert_eq!(input, account_id);
+            assert_eq!(Err(err), AccountId::validate(account_id));
+        }
+
+        let (_, err) = AccountId::validate_owned("alice..near".to_string()).unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::RedundantSeparator);
+        assert_eq!(err.char, Some((6, '.')));
+    }
+
+    #[test]
+    fn test_from_labels() {
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            let account_id: AccountId = account_id.parse().unwrap();
+            let labels: Vec<_> = account_id.labels().collect();
+            assert_eq!(AccountId::from_labels(&labels), Ok(account_id));
+        }
+
+        assert_eq!(AccountId::from_labels(["near"]).unwrap(), "near");
+        assert_eq!(
+            AccountId::from_labels(vec![String::from("alice"), String::from("near")]).unwrap(),
+            "alice.near"
+        );
+
+        let err = AccountId::from_labels(std::iter::empty::<&str>()).unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::TooShort);
+        let err = AccountId::from_labels(["a"]).unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::TooShort);
+        let err = AccountId::from_labels(["alice", "Near"]).unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+        assert_eq!(err.char, Some((6, 'N')));
+        let err = AccountId::from_labels(["", "near"]).unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::TooShort);
+        let err = AccountId::from_labels(["a"; 33]).unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::TooLong);
+    }
+
+    #[test]
+    fn test_url_token() {
+        let account_ids = [
+            "near",
+            "alice.near",
+            "0123456789012345678901234567890123456789012345678901234567890123",
+            "0xb794f5ea0ba39494ce839613fffba74279579268",
+        ];
+        for account_id in account_ids {
+            let account_id: AccountId = account_id.parse().unwrap();
+            let token = account_id.to_url_token();
+            assert!(token
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit()));
+            assert_eq!(AccountId::from_url_token(&token).unwrap(), account_id);
+        }
+
+        // Encodes an invalid Account ID ("a")
+        let err = AccountId::from_url_token("me").unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::TooShort);
+
+        let err = AccountId::from_url_token("MFWGSY3FFZXGKYLS").unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+        assert_eq!(err.char, Some((0, 'M')));
+
+        // Decodes to bytes that aren't UTF-8
+        let token = crate::base32::encode(b"alice\xff.near");
+        assert_eq!(
+            AccountId::from_url_token(&token),
+            AccountId::from_utf8(b"alice\xff.near".to_vec())
+        );
+        let err = AccountId::from_url_token(&token).unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::InvalidUtf8);
+        assert_eq!(err.position(), Some(5));
+    }
+
+    #[test]
+    fn test_box_conversions() {
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            let account_id: AccountId = account_id.parse().unwrap();
+
+            // `AccountId` <-> `Box<AccountIdRef>` reuses the allocation, unless stored inline
+            let copy = account_id.clone();
+            let ptr = copy.as_str().as_ptr();
+            let boxed: Box<AccountIdRef> = copy.into();
+            if !cfg!(feature = "inline") {
+                assert_eq!(boxed.as_str().as_ptr(), ptr);
+            }
+            assert_eq!(*boxed, account_id);
+            let owned = AccountId::from(boxed);
+            if !cfg!(feature = "inline") {
+                assert_eq!(owned.as_str().as_ptr(), ptr);
+            }
+            assert_eq!(owned, account_id);
+
+            // `&AccountIdRef` -> `Box<AccountIdRef>` copies
+            let boxed = Box::<AccountIdRef>::from(&*account_id);
+            assert_ne!(boxed.as_str().as_ptr(), ptr);
+            assert_eq!(*boxed, account_id);
+
+            let cloned = boxed.clone();
+            assert_ne!(cloned.as_str().as_ptr(), boxed.as_str().as_ptr());
+            assert_eq!(cloned, boxed);
+
+            // `Box<str>` -> `Box<AccountIdRef>` validates and reuses the allocation
+            let boxed_str: Box<str> = account_id.as_str().into();
+            let ptr = boxed_str.as_ptr();
+            let boxed = Box::<AccountIdRef>::try_from(boxed_str).unwrap();
+            assert_eq!(boxed.as_str().as_ptr(), ptr);
+            assert_eq!(*boxed, account_id);
+        }
+
+        for account_id in crate::test_data::BAD_ACCOUNT_IDS {
+            let boxed_str: Box<str> = (*account_id).into();
+            assert_eq!(
+                Box::<AccountIdRef>::try_from(boxed_str),
+                Err(AccountId::validate(account_id).unwrap_err())
+            );
+        }
+    }
+
+    #[test]
+    fn test_borrow_lookups() {
+        use std::collections::hash_map::RandomState;
+        use std::collections::{BTreeMap, HashMap};
+        use std::hash::{BuildHasher, Hash};
+
+        fn hash<T: Hash + ?Sized>(state: &RandomState, value: &T) -> u64 {
+            state.hash_one(value)
+        }
+
+        let mut accounts: Vec<AccountId> = crate::test_data::OK_ACCOUNT_IDS
+            .iter()
+            .map(|id| id.parse().unwrap())
+            .collect();
+        accounts.sort();
+        accounts.dedup();
+        let hash_map: HashMap<AccountId, usize> = accounts.iter().cloned().zip(0..).collect();
+        let btree_map: BTreeMap<AccountId, usize> = accounts.iter().cloned().zip(0..).collect();
+
+        let state = RandomState::new();
+        for (idx, account_id) in accounts.iter().enumerate() {
+            let as_str: &str = account_id.as_str();
+            let as_ref: &AccountIdRef = account_id;
+            let as_string: String = account_id.to_string();
+
+            assert_eq!(hash_map.get(as_str), Some(&idx));
+            assert_eq!(hash_map.get(as_ref), Some(&idx));
+            assert_eq!(hash_map.get(as_string.as_str()), Some(&idx));
+            assert_eq!(btree_map.get(as_str), Some(&idx));
+            assert_eq!(btree_map.get(as_ref), Some(&idx));
+            assert_eq!(btree_map.get(as_string.as_str()), Some(&idx));
+
+            // `Borrow` requires the hashes to agree
+            assert_eq!(hash(&state, as_str), hash(&state, account_id));
+            assert_eq!(hash(&state, as_ref), hash(&state, account_id));
+        }
+        // ... and the orderings
+        for pair in accounts.windows(2) {
+            assert_eq!(
+                pair[0].cmp(&pair[1]),
+                pair[0].as_str().cmp(pair[1].as_str())
+            );
+            assert_eq!(
+                (*pair[0]).cmp(&*pair[1]),
+                pair[0].as_str().cmp(pair[1].as_str())
+            );
+        }
+
+        assert_eq!(hash_map.get("Alice.near"), None);
+        assert_eq!(btree_map.get("not.present"), None);
+    }
+
+    #[test]
+    fn test_cow_comparisons() {
+        let alice: AccountId = "alice.near".parse().unwrap();
+        let bob = AccountIdRef::new_or_panic("bob.near");
+        let borrowed: Cow<'_, AccountIdRef> = Cow::Borrowed(&alice);
+        let owned: Cow<'_, AccountIdRef> = Cow::from(alice.clone());
+        assert!(matches!(owned, Cow::Owned(_)));
+
+        for cow in [&borrowed, &owned] {
+            // `str` and `String` are compared through the dereferenced `AccountIdRef`.
+            assert_eq!(**cow, *"alice.near");
+            assert_eq!(**cow, "alice.near");
+            assert_eq!(**cow, "alice.near".to_string());
+
+            assert_eq!(*cow, alice);
+            assert_eq!(alice, *cow);
+            assert_eq!(*cow, *alice);
+            assert_eq!(*alice, *cow);
+            assert_eq!(*cow, &*alice);
+            assert_eq!(&*alice, *cow);
+            assert_ne!(*cow, bob);
+            assert_ne!(bob, *cow);
+
+            assert!(*cow < bob);
+            assert!(bob > *cow);
+            assert!(*cow < *bob);
+            assert!(*bob > *cow);
+            assert!(*cow <= alice);
+            assert!(alice >= *cow);
+            let carol: AccountId = "carol.near".parse().unwrap();
+            assert!(*cow < carol);
+            assert!(carol > *cow);
+        }
+        assert_eq!(borrowed, owned);
+    }
+
+    #[test]
+    fn test_comparison_matrix() {
+        use core::cmp::Ordering;
+
+        // Asserts that `$a` and `$b`, holding "alice.near" and "bob.near", compare like the strings
+        // in both directions, against values of another type holding the same Account IDs.
+        macro_rules! check {
+            ($a:expr, $b:expr, $other_a:expr, $other_b:expr) => {
+                assert!($a == $other_a);
+                assert!($other_a == $a);
+                assert!($a != $other_b);
+                assert!($other_b != $a);
+                assert_eq!($a.partial_cmp(&$other_a), Some(Ordering::Equal));
+                assert_eq!($a.partial_cmp(&$other_b), Some(Ordering::Less));
+                assert_eq!($b.partial_cmp(&$other_a), Some(Ordering::Greater));
+                assert_eq!($other_a.partial_cmp(&$b), Some(Ordering::Less));
+                assert_eq!($other_b.partial_cmp(&$a), Some(Ordering::Greater));
+            };
+        }
+
+        let (alice, bob): (AccountId, AccountId) =
+            ("alice.near".parse().unwrap(), "bob.near".parse().unwrap());
+        let (alice_ref, bob_ref) = (&*alice, &*bob);
+        let (alice_string, bob_string) = (String::from("alice.near"), String::from("bob.near"));
+        let (alice_str, bob_str) = ("alice.near", "bob.near");
+
+        check!(alice, bob, *alice_ref, *bob_ref);
+        check!(alice, bob, alice_ref, bob_ref);
+        check!(alice, bob, alice_string, bob_string);
+        check!(alice, bob, *alice_str, *bob_str);
+        check!(alice, bob, alice_str, bob_str);
+
+        check!(*alice_ref, *bob_ref, alice_string, bob_string);
+        check!(*alice_ref, *bob_ref, *alice_str, *bob_str);
+        check!(*alice_ref, *bob_ref, alice_str, bob_str);
+        check!(alice_ref, bob_ref, alice_string, bob_string);
+        check!(alice_ref, bob_ref, *alice_str, *bob_str);
+        check!(alice_ref, bob_ref, alice_str, bob_str);
+
+        let (alice_bytes, bob_bytes) = (&b"alice.near"[..], &b"bob.near"[..]);
+        check!(alice, bob, *alice_bytes, *bob_bytes);
+        check!(alice, bob, alice_bytes, bob_bytes);
+        check!(*alice_ref, *bob_ref, *alice_bytes, *bob_bytes);
+        check!(*alice_ref, *bob_ref, alice_bytes, bob_bytes);
+        // Unequal buffers, including ones that aren't UTF-8
+        assert!(alice != b"alice.near\xff"[..]);
+        assert!(*alice_ref < b"alice.near\xff"[..]);
+        assert!(alice_ref.partial_cmp(&b"alice"[..]) == Some(Ordering::Greater));
+
+        // Consistent with `Ord`
+        for (a, b) in [
+            ("alice.near", "bob.near"),
+            ("a.near", "a-b.near"),
+            ("zz", "aaa"),
+        ] {
+            let (a_id, b_id) = (AccountIdRef::new_or_panic(a), AccountIdRef::new_or_panic(b));
+            assert_eq!(a_id.cmp(b_id), a.cmp(b));
+            assert_eq!(a_id.to_owned().partial_cmp(b_id), Some(a.cmp(b)));
+            assert_eq!(a_id.to_owned().partial_cmp(b), Some(a.cmp(b)));
+        }
+    }
+
+    #[test]
+    fn test_as_ref_bytes() {
+        use std::collections::HashMap;
+
+        fn bytes<T: AsRef<[u8]> + ?Sized>(value: &T) -> &[u8] {
+            value.as_ref()
+        }
+
+        let alice: AccountId = "alice.near".parse().unwrap();
+        assert_eq!(bytes(&alice), b"alice.near");
+        assert_eq!(bytes::<AccountIdRef>(&alice), b"alice.near");
+        assert_eq!(bytes(&alice).as_ptr(), alice.as_str().as_ptr());
+
+        let balances: HashMap<AccountId, u64> = [(alice.clone(), 100)].into_iter().collect();
+        assert_eq!(balances.get("alice.near"), Some(&100));
+        assert_eq!(
+            balances.get(AccountIdRef::new_or_panic("alice.near")),
+            Some(&100)
+        );
+        assert_eq!(balances.get("bob.near"), None);
+    }
+
+    #[test]
+    fn test_common_ancestor_of() {
+        fn common_ancestor_of<'a>(accounts: &[&'a str]) -> Option<&'a AccountIdRef> {
+            AccountId::common_ancestor_of(accounts.iter().map(|id| AccountIdRef::new_or_panic(id)))
+        }
+
+        // Fully nested
+        let nested = ["c.b.a.near", "b.a.near", "d.c.b.a.near", "a.near"];
+        assert_eq!(common_ancestor_of(&nested).unwrap(), "a.near");
+        assert_eq!(common_ancestor_of(&nested[..3]).unwrap(), "b.a.near");
+        assert_eq!(common_ancestor_of(&nested[..1]).unwrap(), "c.b.a.near");
+
+        // Siblings and cousins
+        assert_eq!(
+            common_ancestor_of(&["x.app.near", "y.app.near", "z.w.app.near"]).unwrap(),
+            "app.near"
+        );
+        assert_eq!(
+            common_ancestor_of(&["x.app.near", "x.app.near"]).unwrap(),
+            "x.app.near"
+        );
+        assert_eq!(
+            common_ancestor_of(&["x.app.near", "pp.near"]).unwrap(),
+            "near"
+        );
+
+        // Disjoint
+        assert_eq!(
+            common_ancestor_of(&["x.app.near", "y.app.near", "app.testnet"]),
+            None
+        );
+        assert_eq!(common_ancestor_of(&["near", "testnet"]), None);
+        assert_eq!(common_ancestor_of(&[]), None);
+
+        // Works with owned Account IDs too
+        let owned: Vec<AccountId> = ["alice.near", "bob.near"]
+            .iter()
+            .map(|id| id.parse().unwrap())
+            .collect();
+        assert_eq!(
+            AccountId::common_ancestor_of(owned.iter().map(|id| &**id)).unwrap(),
+            "near"
+        );
+    }
+
+    #[test]
+    fn test_shard_subaccount() {
+        let base = AccountIdRef::new_or_panic("store.near");
+
+        // Deterministic, and pinned to FNV-1a
+        for (key, shard_count, expected) in [
+            (&b""[..], 16, "shard-5.store.near"),
+            (b"alice", 16, "shard-7.store.near"),
+            (b"alice", 1, "shard-0.store.near"),
+            (b"bob", 1000, "shard-92.store.near"),
+        ] {
+            let shard = AccountId::shard_subaccount(base, key, shard_count).unwrap();
+            assert_eq!(shard, expected);
+            assert_eq!(
+                AccountId::shard_subaccount(base, key, shard_count).unwrap(),
+                shard
+            );
+            assert!(shard.is_sub_account_of(base));
+        }
+
+        for idx in 0..100u32 {
+            let key = idx.to_le_bytes();
+            let shard = AccountId::shard_subaccount(base, &key, 7).unwrap();
+            let label = shard.as_str().split('.').next().unwrap();
+            let n: u32 = label.strip_prefix("shard-").unwrap().parse().unwrap();
+            assert!(n < 7);
+        }
+
+        // No room left for the `shard-{idx}.` prefix
+        let long_base = AccountId::try_from("a".repeat(AccountId::MAX_LEN - 7)).unwrap();
+        let err = AccountId::shard_subaccount(&long_base, b"alice", 16).unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::TooLong);
+    }
+
+    #[test]
+    fn test_new_normalized() {
+        for (input, expected) in [
+            ("ALICE.NEAR", "alice.near"),
+            ("Alice.Near", "alice.near"),
+            ("App_V2-X.alice.near", "app_v2-x.alice.near"),
+            ("alice.near", "alice.near"),
+        ] {
+            assert_eq!(AccountId::new_normalized(input).unwrap(), expected);
+            assert_eq!(
+                AccountId::new_normalized(String::from(input)).unwrap(),
+                expected
+            );
+            assert_eq!(
+                AccountIdRef::new_normalized(input).unwrap().as_str(),
+                expected
+            );
+        }
+
+        // Lowercase inputs are borrowed
+        let input = "alice.near";
+        match AccountIdRef::new_normalized(input).unwrap() {
+            Cow::Borrowed(account_id) => assert_eq!(account_id.as_str().as_ptr(), input.as_ptr()),
+            Cow::Owned(_) => panic!("lowercase input was copied"),
+        }
+
+        // Nothing else is fixed up, and errors point into the original input
+        let error = |kind, idx, char| ParseAccountError {
+            kind,
+            char: Some((idx, char)),
+            len: None,
+        };
+        for (input, expected) in [
+            ("Аlice.near", error(ParseErrorKind::InvalidChar, 0, 'А')),
+            ("ƑELICIA.NEAR", error(ParseErrorKind::InvalidChar, 0, 'Ƒ')),
+            ("ALICE@NEAR", error(ParseErrorKind::InvalidChar, 5, '@')),
+            (" Alice.near", error(ParseErrorKind::InvalidChar, 0, ' ')),
+            (
+                "Alice..Near",
+                error(ParseErrorKind::RedundantSeparator, 6, '.'),
+            ),
+            (
+                "alice.near-",
+                error(ParseErrorKind::RedundantSeparator, 10, '-'),
+            ),
+        ] {
+            assert_eq!(
+                AccountId::new_normalized(input),
+                Err(expected.clone()),
+                "{:?}",
+                input
+            );
+            assert_eq!(AccountIdRef::new_normalized(input), Err(expected));
+        }
+        assert_eq!(
+            AccountId::new_normalized("A"),
+            Err(ParseAccountError::too_short(1))
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        for (input, expected) in [
+            ("  Alice.NEAR  ", "alice.near"),
+            ("\talice.near\r\n", "alice.near"),
+            ("ALICE.NEAR", "alice.near"),
+            (
+                " 0xB794F5EA0BA39494CE839613FFFBA74279579268",
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+            ),
+        ] {
+            assert_eq!(AccountId::normalize(input).unwrap(), expected);
+            assert!(input.parse::<AccountId>().is_err(), "{:?}", input);
+        }
+        // Valid Account IDs are left alone
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            assert_eq!(AccountId::normalize(account_id).unwrap(), *account_id);
+        }
+
+        // Errors point into the original input
+        let error = |kind, idx, char| ParseAccountError {
+            kind,
+            char: Some((idx, char)),
+            len: None,
+        };
+        for (input, expected) in [
+            ("  Alice Near  ", error(ParseErrorKind::InvalidChar, 7, ' ')),
+            (" Аlice.near", error(ParseErrorKind::InvalidChar, 1, 'А')),
+            (
+                "\u{3000}alice@near",
+                error(ParseErrorKind::InvalidChar, 8, '@'),
+            ),
+            (
+                " alice..NEAR",
+                error(ParseErrorKind::RedundantSeparator, 7, '.'),
+            ),
+            (
+                "alice.near. ",
+                error(ParseErrorKind::RedundantSeparator, 10, '.'),
+            ),
+        ] {
+            assert_eq!(AccountId::normalize(input), Err(expected), "{:?}", input);
+        }
+        assert_eq!(
+            AccountId::normalize("  A  "),
+            Err(ParseAccountError::too_short(1))
+        );
+        assert_eq!(
+            AccountId::normalize("   "),
+            Err(ParseAccountError::too_short(0))
+        );
+    }
+
+    #[test]
+    fn test_from_utf8() {
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            let bytes = account_id.as_bytes();
+            assert_eq!(AccountId::try_from(bytes).unwrap(), *account_id);
+            assert_eq!(AccountId::from_utf8(bytes.to_vec()).unwrap(), *account_id);
+        }
+        for account_id in crate::test_data::BAD_ACCOUNT_IDS {
+            let err = AccountId::validate(account_id).unwrap_err();
+            assert_eq!(AccountId::try_from(account_id.as_bytes()), Err(err.clone()));
+            assert_eq!(
+                AccountId::from_utf8(account_id.as_bytes().to_vec()),
+                Err(err)
+            );
+        }
+
+        // Exactly at the length bounds
+        let shortest = b"a".repeat(AccountId::MIN_LEN);
+        assert!(AccountId::try_from(shortest.as_slice()).is_ok());
+        assert_eq!(
+            AccountId::try_from(&shortest[1..]),
+            Err(ParseAccountError::too_short(1))
+        );
+        let longest = b"a".repeat(AccountId::MAX_LEN);
+        assert!(AccountId::from_utf8(longest.clone()).is_ok());
+        let mut too_long = longest;
+        too_long.push(b'a');
+        assert_eq!(
+            AccountId::from_utf8(too_long),
+            Err(ParseAccountError::too_long(65, AccountId::MAX_LEN))
+        );
+
+        // The buffer is reused, unless stored inline
+        let bytes = b"alice.near".to_vec();
+        let ptr = bytes.as_ptr();
+        let alice = AccountId::from_utf8(bytes).unwrap();
+        if !cfg!(feature = "inline") {
+            assert_eq!(alice.as_str().as_ptr(), ptr);
+        }
+
+        let invalid_char = |idx, char| ParseAccountError {
+            kind: ParseErrorKind::InvalidChar,
+            char: Some((idx, char)),
+            len: None,
+        };
+        let invalid_utf8 = |idx| ParseAccountError {
+            kind: ParseErrorKind::InvalidUtf8,
+            char: Some((idx, char::REPLACEMENT_CHARACTER)),
+            len: None,
+        };
+        // Not UTF-8
+        for (bytes, expected) in [
+            (&b"alice\xff.near"[..], invalid_utf8(5)),
+            (b"\xc3\x28near", invalid_utf8(0)),
+            (b"alice.\xe2\x82", invalid_utf8(6)),
+        ] {
+            assert_eq!(AccountId::try_from(bytes), Err(expected.clone()));
+            assert_eq!(AccountId::from_utf8(bytes.to_vec()), Err(expected.clone()));
+            assert_eq!(
+                AccountId::try_from_utf8(bytes.to_vec()),
+                Err(expected.clone())
+            );
+            assert_eq!(AccountIdRef::from_utf8(bytes), Err(expected));
+        }
+        assert_eq!(
+            AccountId::try_from_utf8(b"alice\xff.near".to_vec())
+                .unwrap_err()
+                .to_string(),
+            "the Account ID is not valid UTF-8 at index 5"
+        );
+        // Errors before the invalid byte come first
+        let err = AccountId::try_from(&b"alice..\xff"[..]).unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::RedundantSeparator);
+        assert_eq!(err.char, Some((6, '.')));
+        // Valid UTF-8 beyond ASCII is reported at its byte offset
+        assert_eq!(
+            AccountId::try_from("alice.ƒ".as_bytes()),
+            Err(invalid_char(6, 'ƒ'))
+        );
+        // Embedded NULs
+        assert_eq!(
+            AccountId::try_from(&b"alice\0near"[..]),
+            Err(invalid_char(5, '\0'))
+        );
+        assert_eq!(
+            AccountId::from_utf8(b"alice.near\0".to_vec()),
+            Err(invalid_char(10, '\0'))
+        );
+    }
+
+    #[test]
+    fn test_bytes_roundtrip() {
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            let bytes = account_id.to_bytes();
+            assert_eq!(bytes[0] as usize, account_id.len());
+            assert_eq!(&bytes[1..], account_id.as_bytes());
+            assert_eq!(AccountId::from_bytes(&bytes).unwrap(), account_id);
+        }
+        let longest = AccountId::try_from("a".repeat(AccountId::MAX_LEN)).unwrap();
+        assert_eq!(AccountId::from_bytes(&longest.to_bytes()), Ok(longest));
+    }
+
+    #[test]
+    fn test_from_bytes_invalid() {
+        let invalid_length_prefix = ParseAccountError {
+            kind: ParseErrorKind::InvalidLengthPrefix,
+            char: None,
+            len: None,
+        };
+        let bytes = AccountIdRef::new_or_panic("alice.near").to_bytes();
+        // Truncated anywhere, including the length prefix itself
+        for len in 0..bytes.len() {
+            assert_eq!(
+                AccountId::from_bytes(&bytes[..len]),
+                Err(invalid_length_prefix.clone())
+            );
+        }
+        // Trailing bytes
+        let mut trailing = bytes;
+        trailing.push(b'a');
+        assert_eq!(
+            AccountId::from_bytes(&trailing),
+            Err(invalid_length_prefix.clone())
+        );
+        assert_eq!(
+            invalid_length_prefix.to_string(),
+            "the length prefix doesn't match the encoded Account ID"
+        );
+
+        // A well-formed encoding of an invalid Account ID
+        for account_id in crate::test_data::BAD_ACCOUNT_IDS {
+            let mut bytes = vec![account_id.len() as u8];
+            bytes.extend_from_slice(account_id.as_bytes());
+            assert_eq!(
+                AccountId::from_bytes(&bytes),
+                Err(AccountId::validate(account_id).unwrap_err())
+            );
+        }
+        assert_eq!(
+            AccountId::from_bytes(b"\x01a"),
+            Err(ParseAccountError::too_short(1))
+        );
+        assert_eq!(
+            AccountId::from_bytes(b"\x03a\xffb").unwrap_err().kind(),
+            &ParseErrorKind::InvalidUtf8
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "`shard_count` must be non-zero")]
+    fn test_shard_subaccount_zero_shards() {
+        let _ = AccountId::shard_subaccount(AccountIdRef::new_or_panic("near"), b"alice", 0);
+    }
+
+    #[test]
+    #[cfg(all(feature = "internal_unstable", feature = "validate-unchecked"))]
+    #[allow(deprecated)]
+    fn test_new_unvalidated_validates() {
+        for account_id in crate::test_data::OK_ACCOUNT_IDS {
+            assert_eq!(
+                AccountId::new_unvalidated(account_id.to_string()),
+                account_id.parse::<AccountId>().unwrap()
+            );
+        }
+        for account_id in crate::test_data::BAD_ACCOUNT_IDS {
+            let err =
+                std::panic::catch_unwind(|| AccountId::new_unvalidated(account_id.to_string()))
+                    .unwrap_err();
+            let message = err.downcast_ref::<String>().unwrap();
+            assert_eq!(
+                *message,
+                format!(
+                    "invalid Account ID {:?}: {}",
+                    account_id,
+                    AccountId::validate(account_id).unwrap_err()
+                )
+            );
+        }
+    }
+
     #[test]
     #[cfg(feature = "arbitrary")]
     fn test_arbitrary() {
@@ -378,12 +1885,18 @@ mod tests {
     fn test_schemars() {
         let schema = schemars::schema_for!(AccountId);
         let json_schema = serde_json::to_value(&schema).unwrap();
-        dbg!(&json_schema);
         assert_eq!(
             json_schema,
             serde_json::json!({
                     "$schema": "http://json-schema.org/draft-07/schema#",
-                    "description": "NEAR Account Identifier.\n\nThis is a unique, syntactically valid, human-readable account identifier on the NEAR network.\n\n[See the crate-level docs for information about validation.](index.html#account-id-rules)\n\nAlso see [Error kind precedence](AccountId#error-kind-precedence).\n\n## Examples\n\n``` use unc_account_id::AccountId;\n\nlet alice: AccountId = \"alice.near\".parse().unwrap();\n\nassert!(\"ƒelicia.near\".parse::<AccountId>().is_err()); // (ƒ is not f) ```",
+                    "description": "NEAR Account ID: lower-case letters and digits, split by `.`, `-` or `_` separators which can't lead, trail or follow each other. See https://docs.near.org/docs/concepts/account#account-id-rules",
+                    "examples": [
+                        "alice.near",
+                        "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de"
+                    ],
+                    "maxLength": 64,
+                    "minLength": 2,
+                    "pattern": "^(([a-z\\d]+[\\-_])*[a-z\\d]+\\.)*([a-z\\d]+[\\-_])*[a-z\\d]+$",
                     "title": "AccountId",
                     "type": "string"
                 }
@@ -1,6 +1,14 @@
-use std::borrow::Cow;
-
-use crate::{AccountId, ParseAccountError};
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    rc::Rc,
+    string::String,
+    vec::Vec,
+};
+
+use crate::{AccountId, Ancestors, ImplicitError, Labels, ParseAccountError};
 
 /// Account identifier. This is the human readable UTF-8 string which is used internally to index
 /// accounts on the network and their respective state.
@@ -26,8 +34,7 @@ use crate::{AccountId, ParseAccountError};
 /// [`FromStr`]: std::str::FromStr
 /// [`Path`]: std::path::Path
 #[derive(Debug, PartialEq, PartialOrd, Ord, Eq, Hash)]
-#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
-#[cfg_attr(feature = "abi", derive(borsh::BorshSchema))]
+#[repr(transparent)]
 pub struct AccountIdRef(pub(crate) str);
 
 /// Enum representing possible types of accounts.
@@ -36,7 +43,7 @@ pub struct AccountIdRef(pub(crate) str);
 ///
 /// [`get_account_type`]: AccountIdRef::get_account_type
 /// [`AccountIdRef`]: struct.AccountIdRef.html
-#[derive(PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AccountType {
     /// Any valid account, that is neither NEAR-implicit nor ETH-implicit.
     NamedAccount,
@@ -47,6 +54,7 @@ pub enum AccountType {
 }
 
 impl AccountType {
+    /// Returns `true` for NEAR-implicit and ETH-implicit accounts.
     pub fn is_implicit(&self) -> bool {
         match &self {
             Self::NearImplicitAccount => true,
@@ -61,6 +69,11 @@ impl AccountIdRef {
     pub const MIN_LEN: usize = crate::validation::MIN_LEN;
     /// Longest valid length for a NEAR Account ID.
     pub const MAX_LEN: usize = crate::validation::MAX_LEN;
+    /// Shortest length of a top-level Account ID that anyone can create.
+    ///
+    /// Shorter top-level accounts can only be created by the registrar account.
+    /// Also see [`AccountIdRef::requires_registrar`].
+    pub const REGISTRAR_ACCOUNT_MIN_LEN: usize = 32;
 
     /// Construct a [`&AccountIdRef`](AccountIdRef) from a string reference.
     ///
@@ -77,16 +90,90 @@ impl AccountIdRef {
 
     /// Construct a [`&AccountIdRef`](AccountIdRef) from with validation at compile time.
     /// This constructor will panic if validation fails.
+    ///
+    /// In a `const` context, an invalid ID fails the build. Also see the [`account_id!`](crate::account_id) macro.
     /// ```rust
     /// use unc_account_id::AccountIdRef;
     /// const ALICE: &AccountIdRef = AccountIdRef::new_or_panic("alice.near");
     /// ```
     pub const fn new_or_panic(id: &str) -> &Self {
-        crate::validation::validate_const(id);
+        crate::validation::validate_or_panic_const(id);
 
         unsafe { &*(id as *const str as *const Self) }
     }
 
+    /// Construct a [`&AccountIdRef`](AccountIdRef) from raw bytes, borrowing them.
+    ///
+    /// Bytes that aren't valid UTF-8 are reported as
+    /// [`ParseErrorKind::InvalidUtf8`](crate::ParseErrorKind::InvalidUtf8) at their byte offset. See [`AccountId::from_utf8`](crate::AccountId::from_utf8) for the owned version.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountIdRef, ParseErrorKind};
+    ///
+    /// let alice = AccountIdRef::from_utf8(b"alice.near").unwrap();
+    /// assert_eq!(alice, "alice.near");
+    ///
+    /// let err = AccountIdRef::from_utf8(b"alice\xff.near").unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidUtf8);
+    /// ```
+    pub fn from_utf8(bytes: &[u8]) -> Result<&Self, ParseAccountError> {
+        let id = crate::validation::validate_bytes(bytes)?;
+        Ok(Self::new_unvalidated(id))
+    }
+
+    /// Construct an Account ID from a string reference, turning ASCII upper-case letters into lower-case first.
+    ///
+    /// Upper-case letters are never valid, so this doesn't change the meaning of a valid Account ID.
+    /// Nothing else is fixed up, and since lowercasing doesn't move characters, errors point at the same
+    /// positions in `id`. The input is only copied if it contains upper-case letters.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use unc_account_id::{AccountIdRef, ParseErrorKind};
+    ///
+    /// let alice = AccountIdRef::new_normalized("Alice.Near").unwrap();
+    /// assert_eq!(alice.as_str(), "alice.near");
+    ///
+    /// assert!(matches!(AccountIdRef::new_normalized("alice.near"), Ok(Cow::Borrowed(_))));
+    ///
+    /// let err = AccountIdRef::new_normalized("Alice@Near").unwrap_err();
+    /// assert_eq!(err.to_string(), "the Account ID contains an invalid character '@' at index 5");
+    /// ```
+    pub fn new_normalized(id: &str) -> Result<Cow<'_, Self>, ParseAccountError> {
+        if id.bytes().any(|b| b.is_ascii_uppercase()) {
+            AccountId::try_from(id.to_ascii_lowercase()).map(Cow::Owned)
+        } else {
+            Self::new(id).map(Cow::Borrowed)
+        }
+    }
+
+    /// Returns the longest prefix of `input` that is a valid Account ID and ends on a label boundary,
+    /// i.e. all of `input` or the part before one of its `.` separators.
+    ///
+    /// This is meant for interactive input, to show which part of what has been typed so far is usable.
+    /// Returns `None` if no such prefix is valid.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let prefix = AccountIdRef::longest_valid_prefix("alice.near.extra@").unwrap();
+    /// assert_eq!(prefix, "alice.near");
+    ///
+    /// assert_eq!(AccountIdRef::longest_valid_prefix("@alice.near"), None);
+    /// ```
+    pub fn longest_valid_prefix(input: &str) -> Option<&Self> {
+        core::iter::once(input.len())
+            .chain(input.rmatch_indices('.').map(|(idx, _)| idx))
+            .filter(|&end| end <= Self::MAX_LEN)
+            .find_map(|end| Self::new(&input[..end]).ok())
+    }
+
     /// Construct a [`&AccountIdRef`](AccountIdRef) from a string reference without validating the address.
     /// It is the responsibility of the caller to ensure the account ID is valid.
     ///
@@ -97,8 +184,10 @@ impl AccountIdRef {
         // In order to avoid protocol change, `internal_unstable` feature was implemented and it is
         // expected that AccountId might be invalid and it will be explicitly validated at the
         // later stage.
-        #[cfg(not(feature = "internal_unstable"))]
-        debug_assert!(crate::validation::validate(id).is_ok());
+        #[cfg(all(debug_assertions, not(feature = "internal_unstable")))]
+        if let Err(err) = crate::validation::validate(id) {
+            panic!("invalid Account ID {:?}: {}", id, err);
+        }
 
         // Safety: see `AccountIdRef::new`
         unsafe { &*(id as *const str as *const Self) }
@@ -109,6 +198,30 @@ impl AccountIdRef {
         self.0.as_bytes()
     }
 
+    /// Encodes the Account ID as a single length byte followed by its ASCII bytes.
+    ///
+    /// This is a compact wire format that doesn't depend on the serde data model, e.g. for
+    /// `postcard` in embedded contexts. It is decoded by [`AccountId::from_bytes`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountId, AccountIdRef};
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    ///
+    /// let bytes = alice.to_bytes();
+    /// assert_eq!(bytes, b"\x0aalice.near");
+    /// assert_eq!(AccountId::from_bytes(&bytes).unwrap(), alice);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(1 + self.len());
+        // Valid Account IDs are at most `MAX_LEN` bytes long, so the length fits in a byte.
+        bytes.push(self.len() as u8);
+        bytes.extend_from_slice(self.as_bytes());
+        bytes
+    }
+
     /// Returns a string slice of the entire Account ID.
     ///
     /// ## Examples
@@ -143,6 +256,143 @@ impl AccountIdRef {
         !self.is_system() && !self.0.contains('.')
     }
 
+    /// Returns `true` if only the registrar account can create this account, i.e. if it is a named
+    /// top-level account shorter than [`AccountIdRef::REGISTRAR_ACCOUNT_MIN_LEN`].
+    ///
+    /// Sub-accounts are created by their parent, and implicit accounts by transferring to them,
+    /// so neither requires the registrar.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// assert!(AccountIdRef::new_or_panic("near").requires_registrar());
+    /// assert!(!AccountIdRef::new_or_panic("alice.near").requires_registrar());
+    /// assert!(!AccountIdRef::new_or_panic("a-very-long-top-level-account-name").requires_registrar());
+    /// ```
+    pub fn requires_registrar(&self) -> bool {
+        self.is_top_level() && !self.is_implicit() && self.len() < Self::REGISTRAR_ACCOUNT_MIN_LEN
+    }
+
+    /// Returns the name of the [top-level parent](AccountIdRef::top_level_parent) of a named Account ID,
+    /// e.g. `near` or `testnet`.
+    ///
+    /// This is the top-level account the Account ID lives under, which tells the network apart
+    /// by convention. No network names are known to this crate, so it's up to the caller to interpret it.
+    /// It is usually the last label, but not when that is too short to be an Account ID:
+    /// the suffix of `app.near.a` is `near.a`. Returns `None` for implicit accounts, which aren't under
+    /// any top-level account.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// assert_eq!(AccountIdRef::new_or_panic("app.alice.near").network_suffix(), Some("near"));
+    /// assert_eq!(AccountIdRef::new_or_panic("testnet").network_suffix(), Some("testnet"));
+    ///
+    /// let implicit = AccountIdRef::new_or_panic("0xb794f5ea0ba39494ce839613fffba74279579268");
+    /// assert_eq!(implicit.network_suffix(), None);
+    /// ```
+    pub fn network_suffix(&self) -> Option<&str> {
+        if self.is_implicit() {
+            return None;
+        }
+        Some(self.top_level_parent().as_str())
+    }
+
+    /// Returns `true` if the Account ID is the top-level account `tla`, or one of its sub-accounts at any depth.
+    ///
+    /// This is the same as comparing [`network_suffix`](AccountIdRef::network_suffix) with `tla`,
+    /// so it is always `false` for implicit accounts, and if `tla` isn't a top-level account.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let near = AccountIdRef::new_or_panic("near");
+    /// assert!(AccountIdRef::new_or_panic("alice.near").is_under_tla(near));
+    /// assert!(near.is_under_tla(near));
+    /// assert!(!AccountIdRef::new_or_panic("bob.testnet").is_under_tla(near));
+    /// ```
+    pub fn is_under_tla(&self, tla: &AccountIdRef) -> bool {
+        self.network_suffix() == Some(tla.as_str())
+    }
+
+    /// Returns the root of the hierarchy the Account ID belongs to, usually its last label.
+    ///
+    /// This is the crate's one notion of the top level, which [`network_suffix`](AccountIdRef::network_suffix)
+    /// and [`depth`](AccountIdRef::depth) build on. An Account ID without a `.` is its own root, so the
+    /// result always satisfies [`is_top_level`](AccountIdRef::is_top_level),
+    /// [`is_implicit`](AccountIdRef::is_implicit) or [`is_system`](AccountIdRef::is_system).
+    /// The only exception is a last label too short to be an Account ID on its own, like `a` in
+    /// `app.near.a`: then the root is the shortest valid suffix, `near.a`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let near = AccountIdRef::new_or_panic("near");
+    /// assert_eq!(AccountIdRef::new_or_panic("app.alice.near").top_level_parent(), near);
+    /// assert_eq!(near.top_level_parent(), near);
+    ///
+    /// let implicit = AccountIdRef::new_or_panic("0xb794f5ea0ba39494ce839613fffba74279579268");
+    /// assert_eq!(implicit.top_level_parent(), implicit);
+    /// ```
+    pub fn top_level_parent(&self) -> &AccountIdRef {
+        self.ancestors_with_self()
+            .next_back()
+            .expect("an Account ID is one of its own ancestors")
+    }
+
+    /// Returns the number of `.`-separated labels in the Account ID.
+    ///
+    /// Implicit accounts contain no `.`, so they always consist of a single label.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// assert_eq!(AccountIdRef::new_or_panic("near").num_labels(), 1);
+    /// assert_eq!(AccountIdRef::new_or_panic("alice.near").num_labels(), 2);
+    /// assert_eq!(AccountIdRef::new_or_panic("app.alice.near").num_labels(), 3);
+    ///
+    /// let near_rando = AccountIdRef::new_or_panic("98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de");
+    /// assert_eq!(near_rando.num_labels(), 1);
+    /// ```
+    pub fn num_labels(&self) -> usize {
+        self.labels().len()
+    }
+
+    /// Returns the number of levels in the account hierarchy, from the top-level account down to this one.
+    ///
+    /// This counts the [labels](AccountIdRef::labels) in front of the
+    /// [top-level parent](AccountIdRef::top_level_parent), plus one for the top-level parent itself.
+    /// So it is always `self.ancestors().count() + 1`, and top-level, implicit and system accounts
+    /// have a depth of 1. It is [`AccountIdRef::num_labels`] unless the top-level parent spans several
+    /// labels, as it does for `near.a`: `a` is too short to be an Account ID, so `near.a` has no
+    /// [parent](AccountIdRef::parent) and is a top-level account of its own.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// assert_eq!(AccountIdRef::new_or_panic("near").depth(), 1);
+    /// assert_eq!(AccountIdRef::new_or_panic("alice.near").depth(), 2);
+    /// assert_eq!(AccountIdRef::new_or_panic("app.alice.near").depth(), 3);
+    /// assert_eq!(AccountIdRef::new_or_panic("system").depth(), 1);
+    ///
+    /// assert_eq!(AccountIdRef::new_or_panic("near.a").depth(), 1);
+    /// ```
+    pub fn depth(&self) -> usize {
+        self.num_labels() - self.top_level_parent().num_labels() + 1
+    }
+
     /// Returns `true` if the `AccountId` is a direct sub-account of the provided parent account.
     ///
     /// See [Subaccounts](https://docs.near.org/docs/concepts/account#subaccounts).
@@ -169,7 +419,186 @@ impl AccountIdRef {
         self.0
             .strip_suffix(parent.as_str())
             .and_then(|s| s.strip_suffix('.'))
-            .map_or(false, |s| !s.contains('.'))
+            .is_some_and(|s| !s.contains('.'))
+    }
+
+    /// Returns `true` if both accounts are [sub-accounts](AccountIdRef::is_sub_account_of) of the same parent.
+    ///
+    /// An account is never a sibling of itself. Top-level accounts have no parent, so they
+    /// aren't siblings of anything, not even of each other.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let app1 = AccountIdRef::new_or_panic("app1.alice.near");
+    /// let app2 = AccountIdRef::new_or_panic("app2.alice.near");
+    /// assert!(app1.is_sibling_of(app2));
+    /// assert!(!app1.is_sibling_of(app1));
+    ///
+    /// // `p.lice.near` only looks similar
+    /// assert!(!app1.is_sibling_of(AccountIdRef::new_or_panic("p.lice.near")));
+    ///
+    /// assert!(!AccountIdRef::new_or_panic("near").is_sibling_of(AccountIdRef::new_or_panic("testnet")));
+    /// ```
+    pub fn is_sibling_of(&self, other: &AccountIdRef) -> bool {
+        match (self.parent(), other.parent()) {
+            (Some(parent), Some(other_parent)) => parent == other_parent && self != other,
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the `AccountId` is a sub-account of the provided account at any depth.
+    ///
+    /// Unlike [`is_sub_account_of`](AccountIdRef::is_sub_account_of), this doesn't require
+    /// `ancestor` to be the direct parent. An account is never a descendant of itself.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let near = AccountIdRef::new_or_panic("near");
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    /// let app = AccountIdRef::new_or_panic("x.app.alice.near");
+    ///
+    /// assert!(app.is_descendant_of(alice));
+    /// assert!(app.is_descendant_of(near));
+    /// assert!(!alice.is_descendant_of(alice));
+    ///
+    /// // Only whole labels are matched
+    /// assert!(!app.is_descendant_of(AccountIdRef::new_or_panic("lice.near")));
+    /// ```
+    pub fn is_descendant_of(&self, ancestor: &AccountIdRef) -> bool {
+        self.0
+            .strip_suffix(ancestor.as_str())
+            .is_some_and(|s| s.ends_with('.'))
+    }
+
+    /// Returns `true` if the `AccountId` is the provided account, or one of its sub-accounts at any depth.
+    ///
+    /// This is the reflexive version of [`is_descendant_of`](AccountIdRef::is_descendant_of), e.g. for
+    /// permissions granted to an account that also apply to all of its sub-accounts.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    /// assert!(AccountIdRef::new_or_panic("x.app.alice.near").is_self_or_descendant_of(alice));
+    /// assert!(alice.is_self_or_descendant_of(alice));
+    /// assert!(!AccountIdRef::new_or_panic("malice.near").is_self_or_descendant_of(alice));
+    /// ```
+    pub fn is_self_or_descendant_of(&self, ancestor: &AccountIdRef) -> bool {
+        self == ancestor || self.is_descendant_of(ancestor)
+    }
+
+    /// Returns `true` if the provided account is a sub-account of this `AccountId` at any depth.
+    ///
+    /// This is the mirror of [`is_descendant_of`](AccountIdRef::is_descendant_of).
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let near = AccountIdRef::new_or_panic("near");
+    /// assert!(near.is_ancestor_of(AccountIdRef::new_or_panic("app.alice.near")));
+    /// assert!(!near.is_ancestor_of(near));
+    /// ```
+    pub fn is_ancestor_of(&self, descendant: &AccountIdRef) -> bool {
+        descendant.is_descendant_of(self)
+    }
+
+    /// Returns `true` if any of `accounts` is this account or one of its sub-accounts at any depth.
+    ///
+    /// Unlike [`is_ancestor_of`](AccountIdRef::is_ancestor_of), an account counts as covering itself.
+    /// Only whole labels are matched.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    /// let bob = AccountIdRef::new_or_panic("bob.near");
+    /// let app = AccountIdRef::new_or_panic("app.alice.near");
+    ///
+    /// assert!(alice.is_ancestor_of_any(&[bob, app]));
+    /// assert!(alice.is_ancestor_of_any(&[alice]));
+    /// assert!(!app.is_ancestor_of_any(&[alice, bob]));
+    /// ```
+    pub fn is_ancestor_of_any(&self, accounts: &[&AccountIdRef]) -> bool {
+        accounts
+            .iter()
+            .any(|account| account.is_self_or_descendant_of(self))
+    }
+
+    /// Returns the part of the Account ID in front of `.{parent}`, if it is a [descendant](AccountIdRef::is_descendant_of)
+    /// of `parent`.
+    ///
+    /// The result is made of one or more whole labels, and it is never empty. An account is not a
+    /// descendant of itself, so this returns `None` if `self` and `parent` are equal.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    /// assert_eq!(AccountIdRef::new_or_panic("app.alice.near").relative_to(alice), Some("app"));
+    /// assert_eq!(AccountIdRef::new_or_panic("x.y.alice.near").relative_to(alice), Some("x.y"));
+    /// assert_eq!(alice.relative_to(alice), None);
+    /// ```
+    pub fn relative_to(&self, parent: &AccountIdRef) -> Option<&str> {
+        self.0.strip_suffix(parent.as_str())?.strip_suffix('.')
+    }
+
+    /// Returns the longest account that both this account and `other` are equal to or descend from.
+    ///
+    /// Only whole labels are matched, and the shared labels must form a valid Account ID on their own.
+    /// Returns `None` if the accounts don't share a top-level account.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let x = AccountIdRef::new_or_panic("x.app.near");
+    /// let y = AccountIdRef::new_or_panic("y.app.near");
+    /// assert_eq!(x.shared_suffix(y).unwrap(), "app.near");
+    ///
+    /// // `pp.near` is only part of `app.near`
+    /// assert_eq!(x.shared_suffix(AccountIdRef::new_or_panic("pp.near")).unwrap(), "near");
+    ///
+    /// assert_eq!(x.shared_suffix(AccountIdRef::new_or_panic("app.testnet")), None);
+    /// ```
+    pub fn shared_suffix(&self, other: &AccountIdRef) -> Option<&AccountIdRef> {
+        self.ancestors_with_self()
+            .find(|suffix| *suffix == other || suffix.is_ancestor_of(other))
+    }
+
+    /// Returns the deepest account that both `a` and `b` are equal to or descend from.
+    ///
+    /// This is [`a.shared_suffix(b)`](AccountIdRef::shared_suffix), so identical accounts give back the whole
+    /// Account ID, and accounts with different top-level accounts give `None`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let common_parent = |a, b| {
+    ///     AccountIdRef::common_parent(AccountIdRef::new_or_panic(a), AccountIdRef::new_or_panic(b))
+    /// };
+    /// assert_eq!(common_parent("a.x.near", "b.x.near").unwrap(), "x.near");
+    /// assert_eq!(common_parent("a.near", "b.org"), None);
+    /// ```
+    #[inline]
+    pub fn common_parent<'a>(a: &'a AccountIdRef, b: &AccountIdRef) -> Option<&'a AccountIdRef> {
+        a.shared_suffix(b)
     }
 
     /// Returns `AccountType::EthImplicitAccount` if the `AccountId` is a 40 characters long hexadecimal prefixed with '0x'.
@@ -206,6 +635,91 @@ impl AccountIdRef {
         AccountType::NamedAccount
     }
 
+    /// Returns `true` if the `AccountId` is an ETH-implicit account, i.e. `0x` followed by 40 lowercase hex characters.
+    ///
+    /// See [`AccountType::EthImplicitAccount`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let eth_rando = AccountIdRef::new_or_panic("0xb794f5ea0ba39494ce839613fffba74279579268");
+    /// assert!(eth_rando.is_eth_implicit());
+    /// assert!(!eth_rando.is_near_implicit());
+    ///
+    /// assert!(!AccountIdRef::new_or_panic("alice.near").is_eth_implicit());
+    /// ```
+    pub fn is_eth_implicit(&self) -> bool {
+        crate::validation::is_eth_implicit(self.as_str())
+    }
+
+    /// Returns `true` if the `AccountId` is a NEAR-implicit account, i.e. 64 lowercase hex characters.
+    ///
+    /// See [`AccountType::NearImplicitAccount`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let near_rando = AccountIdRef::new_or_panic("98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de");
+    /// assert!(near_rando.is_near_implicit());
+    /// assert!(!near_rando.is_eth_implicit());
+    ///
+    /// assert!(!AccountIdRef::new_or_panic("alice.near").is_near_implicit());
+    /// ```
+    pub fn is_near_implicit(&self) -> bool {
+        crate::validation::is_near_implicit(self.as_str())
+    }
+
+    /// Returns `true` if the `AccountId` is an implicit account of either kind.
+    ///
+    /// This is the same as checking [`is_near_implicit`](Self::is_near_implicit) or
+    /// [`is_eth_implicit`](Self::is_eth_implicit), or [`AccountType::is_implicit`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let near_rando = AccountIdRef::new_or_panic("98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de");
+    /// assert!(near_rando.is_implicit());
+    ///
+    /// let eth_rando = AccountIdRef::new_or_panic("0xb794f5ea0ba39494ce839613fffba74279579268");
+    /// assert!(eth_rando.is_implicit());
+    ///
+    /// assert!(!AccountIdRef::new_or_panic("alice.near").is_implicit());
+    /// ```
+    pub fn is_implicit(&self) -> bool {
+        self.get_account_type().is_implicit()
+    }
+
+    /// Returns the 32-byte ED25519 public key encoded by a NEAR-implicit account.
+    ///
+    /// Unlike [`is_near_implicit`](Self::is_near_implicit), this tells apart why an `AccountId`
+    /// isn't usable as an implicit account: a hex string of the wrong length, a 64-character string
+    /// that isn't hex, or an account that isn't implicit at all.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountIdRef, ImplicitError};
+    ///
+    /// let near_rando = AccountIdRef::new_or_panic("98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de");
+    /// let key = near_rando.require_implicit_key().unwrap();
+    /// assert_eq!(key[..2], [0x98, 0x79]);
+    ///
+    /// let truncated = AccountIdRef::new_or_panic("98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6d");
+    /// assert_eq!(truncated.require_implicit_key(), Err(ImplicitError::WrongLength(63)));
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    /// assert_eq!(alice.require_implicit_key(), Err(ImplicitError::NotImplicit));
+    /// ```
+    pub fn require_implicit_key(&self) -> Result<[u8; 32], ImplicitError> {
+        crate::validation::near_implicit_key(self.as_str())
+    }
+
     /// Returns `true` if this `AccountId` is the system account.
     ///
     /// See [System account](https://nomicon.io/DataStructures/Account.html?highlight=system#system-account).
@@ -230,16 +744,439 @@ impl AccountIdRef {
         self.0.len()
     }
 
-    /// Returns parent's account id reference
+    /// Always returns `false`, since Account IDs are at least [`AccountId::MIN_LEN`] characters long.
+    pub const fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if a sub-account with a first label of `label_len` characters can be created under this account.
+    ///
+    /// That requires the sub-account (`<label>.<self>`) to fit in [`AccountId::MAX_LEN`], and this account to not
+    /// be an implicit account, since those can't have sub-accounts.
     ///
     /// ## Examples
+    ///
     /// ```
     /// use unc_account_id::AccountIdRef;
     ///
-    /// let alice: &AccountIdRef = AccountIdRef::new_or_panic("alice.near");
-    /// let parent: &AccountIdRef = alice.get_parent_account_id().unwrap();
-    ///
-    /// assert!(alice.is_sub_account_of(parent));
+    /// let near = AccountIdRef::new_or_panic("near");
+    /// assert!(near.can_be_parent_of_label_len(59));
+    /// assert!(!near.can_be_parent_of_label_len(60));
+    ///
+    /// let near_rando = AccountIdRef::new_or_panic("98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de");
+    /// assert!(!near_rando.can_be_parent_of_label_len(1));
+    /// ```
+    pub fn can_be_parent_of_label_len(&self, label_len: usize) -> bool {
+        !self.is_implicit()
+            && label_len > 0
+            && label_len <= crate::validation::MAX_LEN.saturating_sub(self.len() + 1)
+    }
+
+    /// Returns the longest first label a sub-account of this account can have, or `None` if it can't
+    /// have sub-accounts at all.
+    ///
+    /// This is the room left in [`AccountId::MAX_LEN`] once the `.` separator is accounted for.
+    /// Every label length from 1 up to the result passes [`AccountIdRef::can_be_parent_of_label_len`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let shop = AccountIdRef::new_or_panic("shop.near");
+    /// assert_eq!(shop.max_child_len(), Some(54));
+    ///
+    /// let near_rando = AccountIdRef::new_or_panic("98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de");
+    /// assert_eq!(near_rando.max_child_len(), None);
+    /// ```
+    pub fn max_child_len(&self) -> Option<usize> {
+        if !self.can_be_parent_of_label_len(1) {
+            return None;
+        }
+        Some(crate::validation::MAX_LEN - self.len() - 1)
+    }
+
+    /// Returns the parent account, i.e. the part of the Account ID after the first `.`.
+    ///
+    /// Returns `None` for top-level accounts (including implicit accounts and the system account),
+    /// and for accounts whose would-be parent is not a valid Account ID itself (e.g. `near.a`).
+    ///
+    /// If `child.parent() == Some(parent)`, then `child.is_sub_account_of(parent)` holds.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let app = AccountIdRef::new_or_panic("app.alice.near");
+    /// let alice = app.parent().unwrap();
+    /// assert_eq!(alice, "alice.near");
+    /// assert!(app.is_sub_account_of(alice));
+    ///
+    /// let near = alice.parent().unwrap();
+    /// assert_eq!(near, "near");
+    /// assert!(near.parent().is_none());
+    /// ```
+    pub fn parent(&self) -> Option<&AccountIdRef> {
+        let parent = self.as_str().split_once('.')?.1;
+        // A suffix of a valid Account ID that starts right after a `.` only
+        // breaks the rules if it is too short, e.g. `a` in `near.a`.
+        if parent.len() < crate::validation::MIN_LEN {
+            return None;
+        }
+        Some(AccountIdRef::new_unvalidated(parent))
+    }
+
+    /// Creates the sub-account `{child}.{self}`, in a single allocation.
+    ///
+    /// `child` is validated with [`validate_new_label`](crate::validate_new_label), so the errors are the same:
+    /// [`TooLong`](crate::ParseErrorKind::TooLong) if the result would be longer than [`AccountId::MAX_LEN`],
+    /// otherwise the first violation in `child`, such as a `.` reported as
+    /// [`InvalidChar`](crate::ParseErrorKind::InvalidChar).
+    /// Since `child` comes first in the result, indices are positions in both `child` and the result.
+    ///
+    /// On success, the result is always a [sub-account](AccountIdRef::is_sub_account_of) of `self`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountIdRef, ParseErrorKind};
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    ///
+    /// let app = alice.join("app").unwrap();
+    /// assert_eq!(app, "app.alice.near");
+    /// assert!(app.is_sub_account_of(alice));
+    ///
+    /// let err = alice.join("my@app").unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+    /// assert_eq!(err.to_string(), "the Account ID contains an invalid character '@' at index 2");
+    ///
+    /// let err = alice.join(&"a".repeat(64)).unwrap_err();
+    /// assert_eq!(err.kind(), &ParseErrorKind::TooLong);
+    /// ```
+    pub fn join(&self, child: &str) -> Result<AccountId, ParseAccountError> {
+        crate::validation::join_labels([child], Some(self))
+    }
+
+    /// Encodes the Account ID as an opaque token for use in URLs.
+    ///
+    /// The token is the lowercase, unpadded base32 encoding of the Account ID bytes.
+    /// Use [`AccountId::from_url_token`] to decode it back.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountId, AccountIdRef};
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    /// let token = alice.to_url_token();
+    /// assert_eq!(token, "mfwgsy3ffzxgkyls");
+    ///
+    /// assert_eq!(AccountId::from_url_token(&token).unwrap(), alice);
+    /// ```
+    pub fn to_url_token(&self) -> String {
+        crate::base32::encode(self.as_bytes())
+    }
+
+    /// Shortens the Account ID to at most `max` characters for display, replacing its middle with `…`.
+    ///
+    /// Account IDs that fit are returned as they are. Otherwise their start and end are kept, with the
+    /// start getting the extra character if the split is uneven. A `max` of zero gives an empty string,
+    /// and of one only the ellipsis.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    /// assert_eq!(alice.display_truncated(16), "alice.near");
+    ///
+    /// let implicit = AccountIdRef::new_or_panic("98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de");
+    /// assert_eq!(implicit.display_truncated(12), "98793c…bd6de");
+    /// ```
+    pub fn display_truncated(&self, max: usize) -> Cow<'_, str> {
+        let account_id = self.as_str();
+        // Account IDs are ASCII, but the split is kept on character boundaries regardless.
+        if account_id.chars().count() <= max {
+            return Cow::Borrowed(account_id);
+        }
+        let Some(kept) = max.checked_sub(1) else {
+            return Cow::Borrowed("");
+        };
+        let (head, tail) = (kept - kept / 2, kept / 2);
+        let head_end = account_id
+            .char_indices()
+            .nth(head)
+            .map_or(account_id.len(), |(idx, _)| idx);
+        let tail_start = account_id
+            .char_indices()
+            .rev()
+            .take(tail)
+            .last()
+            .map_or(account_id.len(), |(idx, _)| idx);
+        Cow::Owned(alloc::format!(
+            "{}…{}",
+            &account_id[..head_end],
+            &account_id[tail_start..]
+        ))
+    }
+
+    /// Derives a fixed-size, 128-bit identifier from the Account ID.
+    ///
+    /// These are the first 16 bytes of the SHA-256 hash of the Account ID bytes. The algorithm is
+    /// part of the contract of this function, so the result is the same across platforms and releases.
+    /// The bytes are not a valid UUID of any version, they are only shaped like one.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let alice = AccountIdRef::new_or_panic("alice.near");
+    /// assert_eq!(
+    ///     alice.to_uuid_bytes(),
+    ///     [0x2d, 0xd5, 0xdd, 0xa5, 0x40, 0x76, 0x7b, 0x3a, 0x1a, 0xa3, 0x35, 0x44, 0xbc, 0xba, 0x38, 0x04]
+    /// );
+    /// ```
+    #[cfg(feature = "sha2")]
+    pub fn to_uuid_bytes(&self) -> [u8; 16] {
+        use sha2::Digest;
+
+        let hash = sha2::Sha256::digest(self.as_bytes());
+        let mut uuid = [0; 16];
+        uuid.copy_from_slice(&hash[..16]);
+        uuid
+    }
+
+    /// Splits the Account ID into the leading sub-account name and the [parent](AccountIdRef::parent).
+    ///
+    /// The leading part is a plain string, since a single label is not necessarily a
+    /// valid Account ID on its own (e.g. `a` in `a.near`).
+    /// If the account has no parent, the whole Account ID is returned as the leading part.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let app = AccountIdRef::new_or_panic("app.alice.near");
+    /// let (name, parent) = app.split_parent();
+    /// assert_eq!(name, "app");
+    /// assert_eq!(parent.unwrap(), "alice.near");
+    ///
+    /// let near = AccountIdRef::new_or_panic("near");
+    /// assert_eq!(near.split_parent(), ("near", None));
+    /// ```
+    pub fn split_parent(&self) -> (&str, Option<&AccountIdRef>) {
+        match self.parent() {
+            Some(parent) => (&self.0[..self.len() - parent.len() - 1], Some(parent)),
+            None => (self.as_str(), None),
+        }
+    }
+
+    /// Splits off the first label of the Account ID, like [`str::split_once`] on `.`, but with a typed remainder.
+    ///
+    /// Returns `None` if the account has no [parent](AccountIdRef::parent), which includes
+    /// single-label and implicit accounts. Also see [`AccountIdRef::split_parent`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let app = AccountIdRef::new_or_panic("app.alice.near");
+    /// let (label, rest) = app.split_once_label().unwrap();
+    /// assert_eq!(label, "app");
+    /// assert_eq!(rest, "alice.near");
+    ///
+    /// assert_eq!(AccountIdRef::new_or_panic("near").split_once_label(), None);
+    /// ```
+    pub fn split_once_label(&self) -> Option<(&str, &AccountIdRef)> {
+        match self.split_parent() {
+            (label, Some(parent)) => Some((label, parent)),
+            (_, None) => None,
+        }
+    }
+
+    /// Splits the Account ID into its leftmost label and the [parent](AccountIdRef::parent) made of the rest.
+    ///
+    /// This is the same as [`AccountIdRef::split_parent`], named to pair with [`AccountIdRef::split_last_label`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let app = AccountIdRef::new_or_panic("app.alice.near");
+    /// let (label, parent) = app.split_first_label();
+    /// assert_eq!(label, "app");
+    /// assert_eq!(parent.unwrap(), "alice.near");
+    /// ```
+    #[inline]
+    pub fn split_first_label(&self) -> (&str, Option<&AccountIdRef>) {
+        self.split_parent()
+    }
+
+    /// Splits the Account ID into the prefix before its rightmost label, and that label.
+    ///
+    /// The prefix is returned as an `AccountIdRef` when it is a valid Account ID on its own,
+    /// and as `None` otherwise, e.g. for `a.near`, where `a` is too short, which still splits off `near`.
+    /// Only an Account ID without a `.`, such as a top-level or implicit account, is returned whole as the label.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let app = AccountIdRef::new_or_panic("app.alice.near");
+    /// let (prefix, label) = app.split_last_label();
+    /// assert_eq!(prefix.unwrap(), "app.alice");
+    /// assert_eq!(label, "near");
+    ///
+    /// let a = AccountIdRef::new_or_panic("a.near");
+    /// assert_eq!(a.split_last_label(), (None, "near"));
+    ///
+    /// let near = AccountIdRef::new_or_panic("near");
+    /// assert_eq!(near.split_last_label(), (None, "near"));
+    /// ```
+    pub fn split_last_label(&self) -> (Option<&AccountIdRef>, &str) {
+        match self.as_str().rsplit_once('.') {
+            // A prefix of a valid Account ID that ends right before a `.` only
+            // breaks the rules if it is too short, e.g. `a` in `a.near`.
+            Some((prefix, label)) => {
+                let prefix = (prefix.len() >= crate::validation::MIN_LEN)
+                    .then(|| AccountIdRef::new_unvalidated(prefix));
+                (prefix, label)
+            }
+            None => (None, self.as_str()),
+        }
+    }
+
+    /// Returns an iterator over the ancestors of this account, starting from its [parent](AccountIdRef::parent)
+    /// and ending with the top-level account.
+    ///
+    /// The iterator doesn't allocate: every item borrows a suffix of this Account ID.
+    /// It can also be reversed to walk the hierarchy from the root down.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let app = AccountIdRef::new_or_panic("app.sub.alice.near");
+    ///
+    /// let ancestors: Vec<_> = app.ancestors().map(AccountIdRef::as_str).collect();
+    /// assert_eq!(ancestors, ["sub.alice.near", "alice.near", "near"]);
+    ///
+    /// assert_eq!(app.ancestors().next_back().unwrap(), "near");
+    ///
+    /// assert_eq!(AccountIdRef::new_or_panic("near").ancestors().count(), 0);
+    /// ```
+    pub fn ancestors(&self) -> Ancestors<'_> {
+        Ancestors::new(self, false)
+    }
+
+    /// Same as [`AccountIdRef::ancestors`], but yields this account first.
+    ///
+    /// Sub-accounts can only be created by their parent, so these are every account that may have
+    /// registered this one, and authority over it can be checked by looking up each of them in turn.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let app = AccountIdRef::new_or_panic("app.alice.near");
+    ///
+    /// let ancestors: Vec<_> = app.ancestors_with_self().map(AccountIdRef::as_str).collect();
+    /// assert_eq!(ancestors, ["app.alice.near", "alice.near", "near"]);
+    /// ```
+    pub fn ancestors_with_self(&self) -> Ancestors<'_> {
+        Ancestors::new(self, true)
+    }
+
+    /// Returns every account that may have registered this account, starting with this account itself
+    /// and walking up to its top-level account.
+    ///
+    /// This yields the same accounts as [`AccountIdRef::ancestors_with_self`], under a name that
+    /// reads better at registrar call sites.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let app = AccountIdRef::new_or_panic("app.alice.near");
+    ///
+    /// let levels: Vec<_> = app.registrar_levels().map(AccountIdRef::as_str).collect();
+    /// assert_eq!(levels, ["app.alice.near", "alice.near", "near"]);
+    /// ```
+    #[inline]
+    pub fn registrar_levels(&self) -> Ancestors<'_> {
+        self.ancestors_with_self()
+    }
+
+    /// Returns an iterator over the `.`-separated labels of the Account ID, from left to right.
+    ///
+    /// Unlike [`AccountIdRef::ancestors`], which yields whole Account IDs, this yields the raw
+    /// label fragments. Labels are never empty, and an Account ID without a `.` (such as an
+    /// implicit account) yields itself as its only label.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let app = AccountIdRef::new_or_panic("app.alice.near");
+    ///
+    /// let labels: Vec<_> = app.labels().collect();
+    /// assert_eq!(labels, ["app", "alice", "near"]);
+    ///
+    /// assert_eq!(app.labels().next_back(), Some("near"));
+    /// assert_eq!(app.labels().len(), app.num_labels());
+    /// ```
+    #[doc(alias = "segments")]
+    pub fn labels(&self) -> Labels<'_> {
+        Labels::new(self)
+    }
+
+    /// Returns the byte offset at which the label at `index` begins, counting labels from the left.
+    ///
+    /// The offset points right after the preceding `.`, and the label runs up to the next `.`
+    /// or the end of the Account ID. Returns `None` if there are no more than `index` labels.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let app = AccountIdRef::new_or_panic("app.alice.near");
+    /// assert_eq!(app.label_start(0), Some(0));
+    /// assert_eq!(app.label_start(1), Some(4));
+    /// assert_eq!(app.label_start(2), Some(10));
+    /// assert_eq!(app.label_start(3), None);
+    /// ```
+    pub fn label_start(&self, index: usize) -> Option<usize> {
+        match index.checked_sub(1) {
+            None => Some(0),
+            Some(dots) => self.0.match_indices('.').nth(dots).map(|(idx, _)| idx + 1),
+        }
+    }
+
+    /// Returns parent's account id reference
+    ///
+    /// This is the same as [`AccountIdRef::parent`].
+    ///
+    /// ## Examples
+    /// ```
+    /// use unc_account_id::AccountIdRef;
+    ///
+    /// let alice: &AccountIdRef = AccountIdRef::new_or_panic("alice.near");
+    /// let parent: &AccountIdRef = alice.get_parent_account_id().unwrap();
+    ///
+    /// assert!(alice.is_sub_account_of(parent));
     ///
     /// let near: &AccountIdRef = AccountIdRef::new_or_panic("near");
     ///
@@ -250,14 +1187,13 @@ impl AccountIdRef {
     /// assert!(implicit.get_parent_account_id().is_none());
     /// ```
     pub fn get_parent_account_id(&self) -> Option<&AccountIdRef> {
-        let parent_str = self.as_str().split_once('.')?.1;
-        Some(AccountIdRef::new_unvalidated(parent_str))
+        self.parent()
     }
 }
 
-impl std::fmt::Display for AccountIdRef {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Display::fmt(&self.0, f)
+impl core::fmt::Display for AccountIdRef {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.0, f)
     }
 }
 
@@ -275,6 +1211,80 @@ impl<'a> From<&'a AccountIdRef> for AccountId {
     }
 }
 
+impl<'a> From<&'a AccountIdRef> for Box<AccountIdRef> {
+    fn from(id: &'a AccountIdRef) -> Self {
+        id.to_owned().into()
+    }
+}
+
+impl TryFrom<Box<str>> for Box<AccountIdRef> {
+    type Error = ParseAccountError;
+
+    fn try_from(value: Box<str>) -> Result<Self, Self::Error> {
+        AccountId::try_from(value).map(Into::into)
+    }
+}
+
+impl Clone for Box<AccountIdRef> {
+    fn clone(&self) -> Self {
+        Self::from(&**self)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<'a> From<&'a AccountIdRef> for Arc<AccountIdRef> {
+    fn from(id: &'a AccountIdRef) -> Self {
+        Arc::<str>::from(id.as_str()).into_account_id_ref()
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl From<AccountId> for Arc<AccountIdRef> {
+    fn from(id: AccountId) -> Self {
+        Arc::<str>::from(id.as_str()).into_account_id_ref()
+    }
+}
+
+impl<'a> From<&'a AccountIdRef> for Rc<AccountIdRef> {
+    fn from(id: &'a AccountIdRef) -> Self {
+        Rc::<str>::from(id.as_str()).into_account_id_ref()
+    }
+}
+
+impl From<AccountId> for Rc<AccountIdRef> {
+    fn from(id: AccountId) -> Self {
+        Rc::<str>::from(id.as_str()).into_account_id_ref()
+    }
+}
+
+/// Reinterprets a shared `str` holding a valid Account ID as a shared `AccountIdRef`.
+trait IntoAccountIdRef {
+    type Output;
+
+    fn into_account_id_ref(self) -> Self::Output;
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl IntoAccountIdRef for Arc<str> {
+    type Output = Arc<AccountIdRef>;
+
+    fn into_account_id_ref(self) -> Self::Output {
+        // Safety:
+        // - a newtype struct is guaranteed to have the same memory layout as its only field
+        // - the pointer comes from `Arc::into_raw`, and is handed back to `Arc::from_raw` right away
+        unsafe { Arc::from_raw(Arc::into_raw(self) as *const AccountIdRef) }
+    }
+}
+
+impl IntoAccountIdRef for Rc<str> {
+    type Output = Rc<AccountIdRef>;
+
+    fn into_account_id_ref(self) -> Self::Output {
+        // Safety: see `IntoAccountIdRef for Arc<str>`
+        unsafe { Rc::from_raw(Rc::into_raw(self) as *const AccountIdRef) }
+    }
+}
+
 impl<'s> TryFrom<&'s str> for &'s AccountIdRef {
     type Error = ParseAccountError;
 
@@ -283,12 +1293,26 @@ impl<'s> TryFrom<&'s str> for &'s AccountIdRef {
     }
 }
 
+impl<'s> TryFrom<&'s Cow<'_, str>> for &'s AccountIdRef {
+    type Error = ParseAccountError;
+
+    fn try_from(value: &'s Cow<'_, str>) -> Result<Self, Self::Error> {
+        AccountIdRef::new(value.as_ref())
+    }
+}
+
 impl AsRef<str> for AccountIdRef {
     fn as_ref(&self) -> &str {
         &self.0
     }
 }
 
+impl AsRef<[u8]> for AccountIdRef {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
 impl PartialEq<AccountIdRef> for String {
     fn eq(&self, other: &AccountIdRef) -> bool {
         self == &other.0
@@ -313,7 +1337,7 @@ impl PartialEq<str> for AccountIdRef {
     }
 }
 
-impl<'a> PartialEq<AccountIdRef> for &'a str {
+impl PartialEq<AccountIdRef> for &str {
     fn eq(&self, other: &AccountIdRef) -> bool {
         *self == &other.0
     }
@@ -331,7 +1355,7 @@ impl<'a> PartialEq<&'a AccountIdRef> for str {
     }
 }
 
-impl<'a> PartialEq<str> for &'a AccountIdRef {
+impl PartialEq<str> for &AccountIdRef {
     fn eq(&self, other: &str) -> bool {
         &self.0 == other
     }
@@ -343,91 +1367,192 @@ impl<'a> PartialEq<&'a AccountIdRef> for String {
     }
 }
 
-impl<'a> PartialEq<String> for &'a AccountIdRef {
+impl PartialEq<String> for &AccountIdRef {
     fn eq(&self, other: &String) -> bool {
         &self.0 == other
     }
 }
 
 impl PartialOrd<AccountIdRef> for String {
-    fn partial_cmp(&self, other: &AccountIdRef) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &AccountIdRef) -> Option<core::cmp::Ordering> {
         self.as_str().partial_cmp(&other.0)
     }
 }
 
 impl PartialOrd<String> for AccountIdRef {
-    fn partial_cmp(&self, other: &String) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &String) -> Option<core::cmp::Ordering> {
         self.0.partial_cmp(other.as_str())
     }
 }
 
 impl PartialOrd<AccountIdRef> for str {
-    fn partial_cmp(&self, other: &AccountIdRef) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &AccountIdRef) -> Option<core::cmp::Ordering> {
         self.partial_cmp(other.as_str())
     }
 }
 
 impl PartialOrd<str> for AccountIdRef {
-    fn partial_cmp(&self, other: &str) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &str) -> Option<core::cmp::Ordering> {
         self.as_str().partial_cmp(other)
     }
 }
 
-impl<'a> PartialOrd<AccountIdRef> for &'a str {
-    fn partial_cmp(&self, other: &AccountIdRef) -> Option<std::cmp::Ordering> {
+impl PartialOrd<AccountIdRef> for &str {
+    fn partial_cmp(&self, other: &AccountIdRef) -> Option<core::cmp::Ordering> {
         self.partial_cmp(&other.as_str())
     }
 }
 
 impl<'a> PartialOrd<&'a str> for AccountIdRef {
-    fn partial_cmp(&self, other: &&'a str) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &&'a str) -> Option<core::cmp::Ordering> {
         self.as_str().partial_cmp(*other)
     }
 }
 
 impl<'a> PartialOrd<&'a AccountIdRef> for String {
-    fn partial_cmp(&self, other: &&'a AccountIdRef) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &&'a AccountIdRef) -> Option<core::cmp::Ordering> {
         self.as_str().partial_cmp(&other.0)
     }
 }
 
-impl<'a> PartialOrd<String> for &'a AccountIdRef {
-    fn partial_cmp(&self, other: &String) -> Option<std::cmp::Ordering> {
+impl PartialOrd<String> for &AccountIdRef {
+    fn partial_cmp(&self, other: &String) -> Option<core::cmp::Ordering> {
         self.0.partial_cmp(other.as_str())
     }
 }
 
 impl<'a> PartialOrd<&'a AccountIdRef> for str {
-    fn partial_cmp(&self, other: &&'a AccountIdRef) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &&'a AccountIdRef) -> Option<core::cmp::Ordering> {
         self.partial_cmp(other.as_str())
     }
 }
 
-impl<'a> PartialOrd<str> for &'a AccountIdRef {
-    fn partial_cmp(&self, other: &str) -> Option<std::cmp::Ordering> {
+impl PartialOrd<str> for &AccountIdRef {
+    fn partial_cmp(&self, other: &str) -> Option<core::cmp::Ordering> {
         self.as_str().partial_cmp(other)
     }
 }
 
-impl<'a> From<&'a AccountIdRef> for Cow<'a, AccountIdRef> {
-    fn from(value: &'a AccountIdRef) -> Self {
-        Cow::Borrowed(value)
+// Byte slices compare against the bytes of the Account ID, e.g. for buffers read off the network.
+
+impl PartialEq<[u8]> for AccountIdRef {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_bytes() == other
     }
 }
 
-#[cfg(feature = "arbitrary")]
-impl<'a> arbitrary::Arbitrary<'a> for &'a AccountIdRef {
-    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
-        (crate::validation::MIN_LEN, Some(crate::validation::MAX_LEN))
+impl PartialEq<AccountIdRef> for [u8] {
+    fn eq(&self, other: &AccountIdRef) -> bool {
+        self == other.as_bytes()
     }
+}
 
-    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
-        let mut s = u.arbitrary::<&str>()?;
+impl<'a> PartialEq<&'a [u8]> for AccountIdRef {
+    fn eq(&self, other: &&'a [u8]) -> bool {
+        self.as_bytes() == *other
+    }
+}
 
-        loop {
-            match AccountIdRef::new(s) {
-                Ok(account_id) => break Ok(account_id),
-                Err(ParseAccountError {
+impl PartialEq<AccountIdRef> for &[u8] {
+    fn eq(&self, other: &AccountIdRef) -> bool {
+        *self == other.as_bytes()
+    }
+}
+
+impl PartialOrd<[u8]> for AccountIdRef {
+    fn partial_cmp(&self, other: &[u8]) -> Option<core::cmp::Ordering> {
+        self.as_bytes().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<AccountIdRef> for [u8] {
+    fn partial_cmp(&self, other: &AccountIdRef) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(other.as_bytes())
+    }
+}
+
+impl<'a> PartialOrd<&'a [u8]> for AccountIdRef {
+    fn partial_cmp(&self, other: &&'a [u8]) -> Option<core::cmp::Ordering> {
+        self.as_bytes().partial_cmp(*other)
+    }
+}
+
+impl PartialOrd<AccountIdRef> for &[u8] {
+    fn partial_cmp(&self, other: &AccountIdRef) -> Option<core::cmp::Ordering> {
+        (*self).partial_cmp(other.as_bytes())
+    }
+}
+
+impl<'a> From<&'a AccountIdRef> for Cow<'a, AccountIdRef> {
+    fn from(value: &'a AccountIdRef) -> Self {
+        Cow::Borrowed(value)
+    }
+}
+
+// `str` and `String` can't be compared with `Cow<'_, AccountIdRef>` directly, as neither type is
+// local to this crate. Dereference the `Cow` instead: `*account_id == "alice.near"`.
+
+impl<'a> PartialEq<AccountIdRef> for Cow<'a, AccountIdRef> {
+    fn eq(&self, other: &AccountIdRef) -> bool {
+        **self == *other
+    }
+}
+
+impl<'a> PartialEq<Cow<'a, AccountIdRef>> for AccountIdRef {
+    fn eq(&self, other: &Cow<'a, AccountIdRef>) -> bool {
+        *self == **other
+    }
+}
+
+impl<'a, 'b> PartialEq<&'b AccountIdRef> for Cow<'a, AccountIdRef> {
+    fn eq(&self, other: &&'b AccountIdRef) -> bool {
+        **self == **other
+    }
+}
+
+impl<'a> PartialEq<Cow<'a, AccountIdRef>> for &AccountIdRef {
+    fn eq(&self, other: &Cow<'a, AccountIdRef>) -> bool {
+        **self == **other
+    }
+}
+
+impl<'a> PartialOrd<AccountIdRef> for Cow<'a, AccountIdRef> {
+    fn partial_cmp(&self, other: &AccountIdRef) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(other)
+    }
+}
+
+impl<'a> PartialOrd<Cow<'a, AccountIdRef>> for AccountIdRef {
+    fn partial_cmp(&self, other: &Cow<'a, AccountIdRef>) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(&**other)
+    }
+}
+
+impl<'a, 'b> PartialOrd<&'b AccountIdRef> for Cow<'a, AccountIdRef> {
+    fn partial_cmp(&self, other: &&'b AccountIdRef) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(*other)
+    }
+}
+
+impl<'a> PartialOrd<Cow<'a, AccountIdRef>> for &AccountIdRef {
+    fn partial_cmp(&self, other: &Cow<'a, AccountIdRef>) -> Option<core::cmp::Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for &'a AccountIdRef {
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (crate::validation::MIN_LEN, Some(crate::validation::MAX_LEN))
+    }
+
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut s = u.arbitrary::<&str>()?;
+
+        loop {
+            match AccountIdRef::new(s) {
+                Ok(account_id) => break Ok(account_id),
+                Err(ParseAccountError {
                     char: Some((idx, _)),
                     ..
                 }) => {
@@ -447,6 +1572,7 @@ impl<'a> arbitrary::Arbitrary<'a> for &'a AccountIdRef {
 
 #[cfg(test)]
 mod tests {
+    use crate::test_data::{BAD_ACCOUNT_IDS, OK_ACCOUNT_IDS};
     use crate::ParseErrorKind;
 
     use super::*;
@@ -460,14 +1586,30 @@ mod tests {
             json_schema,
             serde_json::json!({
                     "$schema": "http://json-schema.org/draft-07/schema#",
-                    "description": "Account identifier. This is the human readable UTF-8 string which is used internally to index accounts on the network and their respective state.\n\nThis is the \"referenced\" version of the account ID. It is to [`AccountId`] what [`str`] is to [`String`], and works quite similarly to [`Path`]. Like with [`str`] and [`Path`], you can't have a value of type `AccountIdRef`, but you can have a reference like `&AccountIdRef` or `&mut AccountIdRef`.\n\nThis type supports zero-copy deserialization offered by [`serde`](https://docs.rs/serde/), but cannot do the same for [`borsh`](https://docs.rs/borsh/) since the latter does not support zero-copy.\n\n# Examples ``` use unc_account_id::{AccountId, AccountIdRef}; use std::convert::{TryFrom, TryInto};\n\n// Construction let alice = AccountIdRef::new(\"alice.near\").unwrap(); assert!(AccountIdRef::new(\"invalid.\").is_err()); ```\n\n[`FromStr`]: std::str::FromStr [`Path`]: std::path::Path",
-                    "title": "AccountIdRef",
+                    "description": "NEAR Account ID: lower-case letters and digits, split by `.`, `-` or `_` separators which can't lead, trail or follow each other. See https://docs.near.org/docs/concepts/account#account-id-rules",
+                    "examples": [
+                        "alice.near",
+                        "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de"
+                    ],
+                    "maxLength": 64,
+                    "minLength": 2,
+                    "pattern": "^(([a-z\\d]+[\\-_])*[a-z\\d]+\\.)*([a-z\\d]+[\\-_])*[a-z\\d]+$",
+                    "title": "AccountId",
                     "type": "string"
                 }
             )
         );
     }
 
+    #[test]
+    #[cfg(all(debug_assertions, not(feature = "internal_unstable")))]
+    #[should_panic(
+        expected = "invalid Account ID \"alice..near\": the Account ID has a redundant separator '.' at index 6"
+    )]
+    fn test_new_unvalidated_invalid() {
+        let _ = AccountIdRef::new_unvalidated("alice..near");
+    }
+
     #[test]
     fn test_err_kind_classification() {
         let id = AccountIdRef::new("ErinMoriarty.near");
@@ -476,7 +1618,8 @@ mod tests {
                 id,
                 Err(ParseAccountError {
                     kind: ParseErrorKind::InvalidChar,
-                    char: Some((0, 'E'))
+                    char: Some((0, 'E')),
+                    len: None,
                 })
             ),
             "{:?}",
@@ -489,7 +1632,8 @@ mod tests {
                 id,
                 Err(ParseAccountError {
                     kind: ParseErrorKind::RedundantSeparator,
-                    char: Some((0, '-'))
+                    char: Some((0, '-')),
+                    len: None,
                 })
             ),
             "{:?}",
@@ -502,7 +1646,8 @@ mod tests {
                 id,
                 Err(ParseAccountError {
                     kind: ParseErrorKind::RedundantSeparator,
-                    char: Some((12, '.'))
+                    char: Some((12, '.')),
+                    len: None,
                 })
             ),
             "{:?}",
@@ -515,7 +1660,8 @@ mod tests {
                 id,
                 Err(ParseAccountError {
                     kind: ParseErrorKind::RedundantSeparator,
-                    char: Some((5, '_'))
+                    char: Some((5, '_')),
+                    len: None,
                 })
             ),
             "{:?}",
@@ -546,7 +1692,7 @@ mod tests {
         ];
         for account_id in ok_top_level_account_ids {
             assert!(
-                AccountIdRef::new(account_id).map_or(false, |account_id| account_id.is_top_level()),
+                AccountIdRef::new(account_id).is_ok_and(|account_id| account_id.is_top_level()),
                 "Valid top level account id {:?} marked invalid",
                 account_id
             );
@@ -592,14 +1738,64 @@ mod tests {
         ];
         for account_id in bad_top_level_account_ids {
             assert!(
-                !AccountIdRef::new(account_id)
-                    .map_or(false, |account_id| account_id.is_top_level()),
+                !AccountIdRef::new(account_id).is_ok_and(|account_id| account_id.is_top_level()),
                 "Invalid top level account id {:?} marked valid",
                 account_id
             );
         }
     }
 
+    // Inputs that are neither sub-accounts nor descendants of the account they are paired with.
+    // Some of them aren't valid Account IDs at all.
+    const NOT_DESCENDANT_PAIRS: &[(&str, &str)] = &[
+        ("test", ".test"),
+        ("test", "test"),
+        ("test", "est"),
+        ("test", ""),
+        ("test", "st"),
+        ("test5", "ббб"),
+        ("test", "a-test"),
+        ("test", "etest"),
+        ("test", "a.etest"),
+        ("test", "retest"),
+        ("test-me", "abc-.test-me"),
+        ("test-me", "Abc.test-me"),
+        ("test-me", "-abc.test-me"),
+        ("test-me", "a--c.test-me"),
+        ("test-me", "a_-c.test-me"),
+        ("test-me", "a-_c.test-me"),
+        ("test-me", "_abc.test-me"),
+        ("test-me", "abc_.test-me"),
+        ("test-me", "..test-me"),
+        ("test-me", "a..test-me"),
+        ("gmail.com", "a.abc@gmail.com"),
+        ("gmail.com", ".abc@gmail.com"),
+        ("gmail.com", ".abc@gmail@com"),
+        ("gmail.com", "abc@gmail@com"),
+        ("test", "a@test"),
+        ("test_me", "abc@test_me"),
+        ("gmail.com", "abc@gmail.com"),
+        ("gmail@com", "abc.gmail@com"),
+        ("gmail.com", "abc-lol@gmail.com"),
+        ("gmail@com", "abc_lol.gmail@com"),
+        ("gmail@com", "bro-abc_lol.gmail@com"),
+        (
+            "gmail.com",
+            "123456789012345678901234567890123456789012345678901234567890@gmail.com",
+        ),
+        (
+            "123456789012345678901234567890123456789012345678901234567890",
+            "1234567890.123456789012345678901234567890123456789012345678901234567890",
+        ),
+        (
+            "b794f5ea0ba39494ce839613fffba74279579268",
+            // ETH-implicit account
+            "0xb794f5ea0ba39494ce839613fffba74279579268",
+        ),
+        ("aa", "ъ@aa"),
+        ("aa", "ъ.aa"),
+    ];
+
     #[test]
     fn test_is_valid_sub_account_id() {
         let ok_pairs = &[
@@ -625,64 +1821,457 @@ mod tests {
             );
         }
 
+        // ... along with descendants that aren't direct sub-accounts
+        let bad_pairs = NOT_DESCENDANT_PAIRS
+            .iter()
+            .chain(&[("test", "a1.a.test"), ("near", "app.alice.near")]);
+        for (signer_id, sub_account_id) in bad_pairs {
+            assert!(
+                !matches!(
+                    (AccountIdRef::new(signer_id), AccountIdRef::new(sub_account_id)),
+                    (Ok(signer_id), Ok(sub_account_id)) if sub_account_id.is_sub_account_of(signer_id)
+                ),
+                "Invalid sub-account {:?} created by account {:?}",
+                sub_account_id,
+                signer_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_descendant_of() {
+        let ok_pairs = &[
+            ("test", "a.test"),
+            ("test", "a1.a.test"),
+            ("test", "x.y.z.test"),
+            ("test-me", "abc.test-me"),
+            ("gmail.com", "abc.gmail.com"),
+            ("gmail.com", "a.abc-lol.gmail.com"),
+            ("gmail.com", "a.b.abc_lol.gmail.com"),
+            ("near", "app.alice.near"),
+            ("alice.near", "x.y.alice.near"),
+            ("g0", "0g.0g.g0"),
+            ("5-3", "1.4_2.5-3"),
+            ("c.near", "a.b.c.near"),
+            ("b.c.near", "a.b.c.near"),
+        ];
+        for (ancestor, descendant) in ok_pairs {
+            let ancestor = AccountIdRef::new(ancestor).unwrap();
+            let descendant = AccountIdRef::new(descendant).unwrap();
+            assert!(
+                descendant.is_descendant_of(ancestor) && ancestor.is_ancestor_of(descendant),
+                "{:?} should be a descendant of {:?}",
+                descendant,
+                ancestor
+            );
+            assert!(descendant.is_self_or_descendant_of(ancestor));
+            assert!(!ancestor.is_self_or_descendant_of(descendant));
+        }
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            assert!(!account_id.is_descendant_of(account_id));
+            assert!(account_id.is_self_or_descendant_of(account_id));
+        }
+
         let bad_pairs = &[
-            ("test", ".test"),
             ("test", "test"),
-            ("test", "a1.a.test"),
             ("test", "est"),
-            ("test", ""),
-            ("test", "st"),
-            ("test5", "ббб"),
             ("test", "a-test"),
             ("test", "etest"),
             ("test", "a.etest"),
-            ("test", "retest"),
-            ("test-me", "abc-.test-me"),
-            ("test-me", "Abc.test-me"),
-            ("test-me", "-abc.test-me"),
-            ("test-me", "a--c.test-me"),
-            ("test-me", "a_-c.test-me"),
-            ("test-me", "a-_c.test-me"),
-            ("test-me", "_abc.test-me"),
-            ("test-me", "abc_.test-me"),
-            ("test-me", "..test-me"),
-            ("test-me", "a..test-me"),
-            ("gmail.com", "a.abc@gmail.com"),
-            ("gmail.com", ".abc@gmail.com"),
-            ("gmail.com", ".abc@gmail@com"),
-            ("gmail.com", "abc@gmail@com"),
-            ("test", "a@test"),
-            ("test_me", "abc@test_me"),
-            ("gmail.com", "abc@gmail.com"),
-            ("gmail@com", "abc.gmail@com"),
-            ("gmail.com", "abc-lol@gmail.com"),
-            ("gmail@com", "abc_lol.gmail@com"),
-            ("gmail@com", "bro-abc_lol.gmail@com"),
+            ("test", "x.y.retest"),
+            ("alice.near", "alice.near"),
+            ("alice.near", "near"),
+            ("alice.near", "app.malice.near"),
+            ("lice.near", "app.alice.near"),
+            ("alice.near", "app.alice.near.org"),
+            ("gmail.com", "gmail.com.abc"),
+            (
+                "b794f5ea0ba39494ce839613fffba74279579268",
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+            ),
+            // Multi-level
+            ("lice.near", "x.y.alice.near"),
+            ("alice.near", "x.y.z.malice.near"),
+            ("y.alice.near", "x.y.malice.near"),
+            ("b.near", "a.b.c.near"),
+            ("a.b.c.near", "b.c.near"),
+        ];
+        for (ancestor, descendant) in bad_pairs.iter().chain(NOT_DESCENDANT_PAIRS) {
+            let (Ok(ancestor), Ok(descendant)) =
+                (AccountIdRef::new(ancestor), AccountIdRef::new(descendant))
+            else {
+                continue;
+            };
+            assert!(
+                !descendant.is_descendant_of(ancestor) && !ancestor.is_ancestor_of(descendant),
+                "{:?} should not be a descendant of {:?}",
+                descendant,
+                ancestor
+            );
+            assert_eq!(
+                descendant.is_self_or_descendant_of(ancestor),
+                descendant == ancestor
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_ancestor_of_any() {
+        let accounts = |ids: &[&'static str]| -> Vec<&'static AccountIdRef> {
+            ids.iter()
+                .map(|id| AccountIdRef::new_or_panic(id))
+                .collect()
+        };
+
+        let resources = accounts(&["x.y.alice.near", "bob.testnet", "gmail.com"]);
+        // Covering
+        for grant in [
+            "near",
+            "alice.near",
+            "y.alice.near",
+            "x.y.alice.near",
+            "testnet",
+            "gmail.com",
+        ] {
+            let grant = AccountIdRef::new_or_panic(grant);
+            assert!(grant.is_ancestor_of_any(&resources), "{}", grant);
+        }
+        // Not covering
+        for grant in [
+            "lice.near",
+            "x.alice.near",
+            "z.x.y.alice.near",
+            "com.gmail",
+            "org",
+            "bob.near",
+        ] {
+            let grant = AccountIdRef::new_or_panic(grant);
+            assert!(!grant.is_ancestor_of_any(&resources), "{}", grant);
+        }
+
+        assert!(!AccountIdRef::new_or_panic("near").is_ancestor_of_any(&[]));
+    }
+
+    #[test]
+    fn test_is_sibling_of() {
+        let siblings = [
+            ("app1.alice.near", "app2.alice.near"),
+            ("app.alice.near", "p.alice.near"),
+            ("alice.near", "bob.near"),
+            ("a.b-a.ra", "c.b-a.ra"),
+        ];
+        let not_siblings = [
+            // Same account
+            ("app.alice.near", "app.alice.near"),
+            // Suffix confusion
+            ("app.alice.near", "p.lice.near"),
+            ("app.alice.near", "lice.near"),
+            ("alice.near", "malice.near.org"),
+            // Different depths
+            ("app.alice.near", "alice.near"),
+            ("x.app.alice.near", "app.alice.near"),
+            // Cousins
+            ("x.app.alice.near", "y.bob.alice.near"),
+            // Top-level accounts
+            ("near", "testnet"),
+            ("near", "near"),
+            (
+                "0123456789012345678901234567890123456789012345678901234567890123",
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+            ),
+            // No valid parent
+            ("x.a", "y.a"),
+        ];
+        for (a, b) in siblings {
+            let (a, b) = (AccountIdRef::new_or_panic(a), AccountIdRef::new_or_panic(b));
+            assert!(a.is_sibling_of(b) && b.is_sibling_of(a), "{} {}", a, b);
+            // Siblings are sub-accounts of the same parent
+            let parent = a.parent().unwrap();
+            assert!(a.is_sub_account_of(parent) && b.is_sub_account_of(parent));
+        }
+        for (a, b) in not_siblings {
+            let (a, b) = (AccountIdRef::new_or_panic(a), AccountIdRef::new_or_panic(b));
+            assert!(!a.is_sibling_of(b) && !b.is_sibling_of(a), "{} {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_relative_to() {
+        let cases = [
+            ("app.alice.near", "alice.near", Some("app")),
+            ("x.y.alice.near", "alice.near", Some("x.y")),
+            ("a.b.abc_lol.gmail.com", "gmail.com", Some("a.b.abc_lol")),
+            ("0g.0g.g0", "g0", Some("0g.0g")),
+            // Equal
+            ("alice.near", "alice.near", None),
+            // Not descendants
+            ("near", "alice.near", None),
+            ("app.malice.near", "alice.near", None),
+            ("app.alice.near.org", "alice.near", None),
+            ("a-test", "test", None),
+        ];
+        for (account_id, parent, expected) in cases {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            let parent = AccountIdRef::new_or_panic(parent);
+            let relative = account_id.relative_to(parent);
+            assert_eq!(relative, expected, "{} {}", account_id, parent);
+            assert_eq!(relative.is_some(), account_id.is_descendant_of(parent));
+            if let Some(relative) = relative {
+                // Joining the parent back gives the original Account ID
+                assert_eq!(format!("{}.{}", relative, parent), account_id.as_str());
+                if !relative.contains('.') {
+                    assert_eq!(parent.join(relative).unwrap(), account_id);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_utf8() {
+        for account_id in OK_ACCOUNT_IDS {
+            let bytes = account_id.as_bytes();
+            let account_id_ref = AccountIdRef::from_utf8(bytes).unwrap();
+            assert_eq!(account_id_ref, *account_id);
+            assert_eq!(account_id_ref.as_bytes().as_ptr(), bytes.as_ptr());
+        }
+
+        let err = AccountIdRef::from_utf8(b"alice.\xe2\x82").unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::InvalidUtf8);
+        assert_eq!(
+            err.to_string(),
+            "the Account ID is not valid UTF-8 at index 6"
+        );
+        assert_eq!(
+            AccountIdRef::from_utf8(b"alice.\xe2\x82\xac")
+                .unwrap_err()
+                .to_string(),
+            "the Account ID contains an invalid character '€' at index 6"
+        );
+    }
+
+    #[test]
+    fn test_shared_pointers() {
+        use std::collections::HashSet;
+
+        fn check<P>(account_id: &AccountId, pointers: [P; 2], ptr_eq: impl Fn(&P, &P) -> bool)
+        where
+            P: Clone + Eq + std::fmt::Debug + std::hash::Hash,
+            P: AsRef<AccountIdRef> + std::borrow::Borrow<AccountIdRef>,
+            P: std::ops::Deref<Target = AccountIdRef>,
+        {
+            let [from_ref, from_owned] = pointers;
+            assert_eq!(&*from_ref, &**account_id);
+            assert_eq!(from_ref.as_ref(), &**account_id);
+            assert_eq!(from_ref.as_str(), account_id.as_str());
+            assert_eq!(from_ref, from_owned);
+            assert!(!ptr_eq(&from_ref, &from_owned));
+
+            let clone = from_ref.clone();
+            assert!(ptr_eq(&clone, &from_ref));
+            assert_eq!(clone, from_ref);
+
+            let set: HashSet<P> = [from_ref, clone, from_owned].into_iter().collect();
+            assert_eq!(set.len(), 1);
+            assert!(set.contains(&**account_id));
+        }
+
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id: AccountId = account_id.parse().unwrap();
+            check(
+                &account_id,
+                [
+                    Arc::<AccountIdRef>::from(&*account_id),
+                    Arc::<AccountIdRef>::from(account_id.clone()),
+                ],
+                Arc::ptr_eq,
+            );
+            check(
+                &account_id,
+                [
+                    Rc::<AccountIdRef>::from(&*account_id),
+                    Rc::<AccountIdRef>::from(account_id.clone()),
+                ],
+                Rc::ptr_eq,
+            );
+        }
+
+        let near = Arc::<AccountIdRef>::from(AccountIdRef::new_or_panic("near"));
+        let handle = {
+            let near = Arc::clone(&near);
+            std::thread::spawn(move || near.is_top_level())
+        };
+        assert!(handle.join().unwrap());
+        assert_eq!(Arc::strong_count(&near), 1);
+    }
+
+    #[test]
+    fn test_shared_suffix() {
+        let cases = [
+            // Full labels
+            ("x.app.near", "y.app.near", Some("app.near")),
+            ("app.near", "x.app.near", Some("app.near")),
+            ("x.app.near", "app.near", Some("app.near")),
+            ("x.app.near", "x.app.near", Some("x.app.near")),
+            ("a.b.c.near", "d.c.near", Some("c.near")),
+            ("alice.near", "bob.near", Some("near")),
+            ("near", "near", Some("near")),
+            // Partial labels don't count
+            ("x.app.near", "pp.near", Some("near")),
+            ("lice.near", "alice.near", Some("near")),
+            ("xnear", "near", None),
+            // Shared labels that aren't a valid Account ID on their own
+            ("x.a", "y.a", None),
+            // Disjoint
+            ("alice.near", "alice.testnet", None),
+            ("near", "testnet", None),
             (
-                "gmail.com",
-                "123456789012345678901234567890123456789012345678901234567890@gmail.com",
+                "0123456789012345678901234567890123456789012345678901234567890123",
+                "near",
+                None,
             ),
+        ];
+        for (account_id, other, expected) in cases {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            let other = AccountIdRef::new_or_panic(other);
+            assert_eq!(
+                account_id.shared_suffix(other).map(AccountIdRef::as_str),
+                expected,
+                "{} {}",
+                account_id,
+                other
+            );
+            assert_eq!(
+                other.shared_suffix(account_id).map(AccountIdRef::as_str),
+                expected
+            );
+        }
+
+        // The result borrows from `self`
+        let a = AccountIdRef::new_or_panic("a.x.near");
+        let suffix = {
+            let b = "b.x.near".parse::<AccountId>().unwrap();
+            a.shared_suffix(&b).unwrap()
+        };
+        assert_eq!(suffix.as_str().as_ptr(), a.as_str()[2..].as_ptr());
+    }
+
+    #[test]
+    fn test_common_parent() {
+        let common_parent = |a, b| {
+            AccountIdRef::common_parent(
+                AccountIdRef::new_or_panic(a),
+                AccountIdRef::new_or_panic(b),
+            )
+            .map(AccountIdRef::as_str)
+        };
+        assert_eq!(common_parent("a.x.near", "b.x.near"), Some("x.near"));
+        assert_eq!(common_parent("c.b.x.near", "b.x.near"), Some("b.x.near"));
+        // Identical inputs
+        assert_eq!(common_parent("a.x.near", "a.x.near"), Some("a.x.near"));
+        assert_eq!(common_parent("near", "near"), Some("near"));
+        // Disjoint top-level accounts
+        assert_eq!(common_parent("a.near", "b.org"), None);
+        assert_eq!(common_parent("x.near", "x.testnet"), None);
+
+        // The result borrows from `a`
+        let a = AccountIdRef::new_or_panic("a.x.near");
+        let parent = {
+            let b = "b.x.near".parse::<AccountId>().unwrap();
+            AccountIdRef::common_parent(a, &b).unwrap()
+        };
+        assert_eq!(parent.as_str().as_ptr(), a.as_str()[2..].as_ptr());
+    }
+
+    #[test]
+    fn test_network_suffix() {
+        let near = AccountIdRef::new_or_panic("near");
+        let testnet = AccountIdRef::new_or_panic("testnet");
+        let cases = [
+            ("alice.near", Some("near")),
+            ("x.y.alice.near", Some("near")),
+            ("bob.testnet", Some("testnet")),
+            ("near", Some("near")),
+            ("testnet", Some("testnet")),
+            ("gmail.com", Some("com")),
+            ("near.a", Some("near.a")),
+            ("app.near.a", Some("near.a")),
             (
-                "123456789012345678901234567890123456789012345678901234567890",
-                "1234567890.123456789012345678901234567890123456789012345678901234567890",
+                "0123456789012345678901234567890123456789012345678901234567890123",
+                None,
             ),
+            ("0xb794f5ea0ba39494ce839613fffba74279579268", None),
+        ];
+        for (account_id, expected) in cases {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            assert_eq!(account_id.network_suffix(), expected, "{}", account_id);
+            assert_eq!(account_id.is_under_tla(near), expected == Some("near"));
+            assert_eq!(
+                account_id.is_under_tla(testnet),
+                expected == Some("testnet")
+            );
+        }
+
+        // Only top-level accounts match
+        let alice = AccountIdRef::new_or_panic("alice.near");
+        assert!(!AccountIdRef::new_or_panic("app.alice.near").is_under_tla(alice));
+        assert!(!alice.is_under_tla(alice));
+        // ... by whole labels
+        assert!(!AccountIdRef::new_or_panic("alice.xnear").is_under_tla(near));
+        let near_a = AccountIdRef::new_or_panic("near.a");
+        assert!(AccountIdRef::new_or_panic("app.near.a").is_under_tla(near_a));
+
+        // The suffix is the top-level parent's name
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            let top_level_parent = account_id.top_level_parent();
+            assert_eq!(
+                account_id.network_suffix(),
+                (!account_id.is_implicit()).then(|| top_level_parent.as_str()),
+                "{}",
+                account_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_top_level_parent() {
+        let cases = [
+            ("near", "near"),
+            ("alice.near", "near"),
+            ("app.alice.near", "near"),
+            ("a.b.c.d.near", "near"),
+            ("bob.testnet", "testnet"),
+            ("system", "system"),
             (
-                "b794f5ea0ba39494ce839613fffba74279579268",
-                // ETH-implicit account
+                "0123456789012345678901234567890123456789012345678901234567890123",
+                "0123456789012345678901234567890123456789012345678901234567890123",
+            ),
+            (
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
                 "0xb794f5ea0ba39494ce839613fffba74279579268",
             ),
-            ("aa", "ъ@aa"),
-            ("aa", "ъ.aa"),
+            // The last label isn't a valid Account ID on its own
+            ("near.a", "near.a"),
+            ("app.near.a", "near.a"),
         ];
-        for (signer_id, sub_account_id) in bad_pairs {
+        for (account_id, expected) in cases {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            assert_eq!(account_id.top_level_parent(), expected, "{}", account_id);
+        }
+
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            let root = account_id.top_level_parent();
+            assert!(root == account_id || root.is_ancestor_of(account_id));
+            let last_label = root.as_str().rsplit('.').next().unwrap();
             assert!(
-                !matches!(
-                    (AccountIdRef::new(signer_id), AccountIdRef::new(sub_account_id)),
-                    (Ok(signer_id), Ok(sub_account_id)) if sub_account_id.is_sub_account_of(&signer_id)
-                ),
-                "Invalid sub-account {:?} created by account {:?}",
-                sub_account_id,
-                signer_id
+                root.is_top_level()
+                    || root.is_implicit()
+                    || root.is_system()
+                    || last_label.len() < AccountIdRef::MIN_LEN,
+                "{}",
+                root
             );
         }
     }
@@ -769,6 +2358,793 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_account_type() {
+        let cases = [
+            ("near", AccountType::NamedAccount),
+            ("system", AccountType::NamedAccount),
+            ("alice.near", AccountType::NamedAccount),
+            ("app.alice.near", AccountType::NamedAccount),
+            ("0x", AccountType::NamedAccount),
+            (
+                "0xb794f5ea0ba39494ce839613fffba74279579268.near",
+                AccountType::NamedAccount,
+            ),
+            (
+                "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+                AccountType::NearImplicitAccount,
+            ),
+            (
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+                AccountType::EthImplicitAccount,
+            ),
+        ];
+        for (account_id, expected) in cases {
+            let account_type = AccountIdRef::new(account_id).unwrap().get_account_type();
+            assert_eq!(account_type, expected, "{:?}", account_id);
+            assert_eq!(
+                account_type.is_implicit(),
+                expected != AccountType::NamedAccount
+            );
+        }
+    }
+
+    #[test]
+    fn test_account_type_agrees_with_predicates() {
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            let account_type = account_id.get_account_type();
+
+            assert_eq!(
+                account_type == AccountType::NearImplicitAccount,
+                account_id.is_near_implicit(),
+                "{:?}",
+                account_id
+            );
+            assert_eq!(
+                account_type == AccountType::EthImplicitAccount,
+                account_id.is_eth_implicit(),
+                "{:?}",
+                account_id
+            );
+            assert_eq!(
+                account_type.is_implicit(),
+                account_id.is_near_implicit() || account_id.is_eth_implicit(),
+                "{:?}",
+                account_id
+            );
+
+            // Implicit accounts are top-level, the system account is neither
+            if account_type.is_implicit() {
+                assert!(account_id.is_top_level(), "{:?}", account_id);
+            }
+            if account_id.is_system() {
+                assert!(account_type == AccountType::NamedAccount);
+                assert!(!account_id.is_top_level());
+            }
+        }
+    }
+
+    #[test]
+    fn test_requires_registrar() {
+        let top_level = |len| AccountIdRef::new(&"a".repeat(len)).unwrap().to_owned();
+
+        assert!(top_level(AccountIdRef::MIN_LEN).requires_registrar());
+        assert!(top_level(31).requires_registrar());
+        assert!(!top_level(AccountIdRef::REGISTRAR_ACCOUNT_MIN_LEN).requires_registrar());
+        assert!(!top_level(33).requires_registrar());
+        assert!(!top_level(AccountIdRef::MAX_LEN).requires_registrar());
+
+        // Sub-accounts of any length
+        for account_id in [
+            "a.near",
+            "app.alice.near",
+            &format!("{}.near", "a".repeat(59)),
+        ] {
+            assert!(!AccountIdRef::new_or_panic(account_id).requires_registrar());
+        }
+        assert!(!AccountIdRef::new_or_panic("a.b").requires_registrar());
+
+        for account_id in [
+            "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+            "0xb794f5ea0ba39494ce839613fffba74279579268",
+            "system",
+        ] {
+            assert!(!AccountIdRef::new_or_panic(account_id).requires_registrar());
+        }
+    }
+
+    #[test]
+    fn test_max_child_len() {
+        let near = AccountIdRef::new_or_panic("near");
+        assert_eq!(near.max_child_len(), Some(59));
+        assert!(near.join(&"a".repeat(59)).is_ok());
+        assert!(near.join(&"a".repeat(60)).is_err());
+
+        // A 62-character account has room for a single character, a 63-character one has none
+        let longest_parent = AccountIdRef::new_or_panic(
+            "01234567890123456789012345678901234567890123456789012345678901",
+        );
+        assert_eq!(longest_parent.max_child_len(), Some(1));
+        assert!(longest_parent.join("a").is_ok());
+        let too_long = AccountIdRef::new_or_panic(
+            "012345678901234567890123456789012345678901234567890123456789012",
+        );
+        assert_eq!(too_long.max_child_len(), None);
+        assert!(too_long.join("a").is_err());
+        let max_len = AccountIdRef::new_or_panic(
+            "0123456789012345678901234567890123456789012345678901234567890123",
+        );
+        assert_eq!(max_len.max_child_len(), None);
+
+        for implicit in [
+            "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+            "0xb794f5ea0ba39494ce839613fffba74279579268",
+        ] {
+            assert_eq!(AccountIdRef::new_or_panic(implicit).max_child_len(), None);
+        }
+
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            assert!(!account_id.is_empty());
+            match account_id.max_child_len() {
+                Some(len) => {
+                    assert!(account_id.can_be_parent_of_label_len(len));
+                    assert!(!account_id.can_be_parent_of_label_len(len + 1));
+                }
+                None => assert!(!account_id.can_be_parent_of_label_len(1)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_can_be_parent_of_label_len() {
+        let near = AccountIdRef::new_or_panic("near");
+        let budget = AccountId::MAX_LEN - near.len() - 1;
+        assert!(near.can_be_parent_of_label_len(1));
+        assert!(near.can_be_parent_of_label_len(budget - 1));
+        assert!(near.can_be_parent_of_label_len(budget));
+        assert!(!near.can_be_parent_of_label_len(budget + 1));
+        assert!(!near.can_be_parent_of_label_len(usize::MAX));
+        // There are no empty labels
+        assert!(!near.can_be_parent_of_label_len(0));
+
+        let longest_parent = AccountIdRef::new_or_panic(
+            "01234567890123456789012345678901234567890123456789012345678901",
+        );
+        assert!(longest_parent.can_be_parent_of_label_len(1));
+        assert!(!longest_parent.can_be_parent_of_label_len(2));
+
+        let max_len = AccountIdRef::new_or_panic(
+            "0123456789012345678901234567890123456789012345678901234567890123",
+        );
+        assert!(!max_len.can_be_parent_of_label_len(1));
+
+        for implicit in [
+            "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+            "0xb794f5ea0ba39494ce839613fffba74279579268",
+        ] {
+            assert!(!AccountIdRef::new_or_panic(implicit).can_be_parent_of_label_len(1));
+        }
+
+        // Agrees with actually building the sub-account
+        for account_id in OK_ACCOUNT_IDS {
+            let parent = AccountIdRef::new(account_id).unwrap();
+            for label_len in [1, 2, 10, 20, 60] {
+                let sub_account = format!("{}.{}", "a".repeat(label_len), parent);
+                assert_eq!(
+                    parent.can_be_parent_of_label_len(label_len),
+                    !parent.is_implicit() && AccountIdRef::new(&sub_account).is_ok(),
+                    "{:?}",
+                    sub_account
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "sha2")]
+    fn test_to_uuid_bytes() {
+        let cases = [
+            ("alice.near", 0x2dd5dda540767b3a1aa33544bcba3804u128),
+            ("near", 0x46ba34770bccfde756708d47d83fb7c8),
+            (
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+                0x2aaa5a1dff01a35843c436ab3a508b19,
+            ),
+        ];
+        for (account_id, expected) in cases {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            assert_eq!(
+                account_id.to_uuid_bytes(),
+                expected.to_be_bytes(),
+                "{}",
+                account_id
+            );
+        }
+
+        let uuids: std::collections::HashSet<_> = OK_ACCOUNT_IDS
+            .iter()
+            .map(|id| AccountIdRef::new_or_panic(id).to_uuid_bytes())
+            .collect();
+        let ids: std::collections::HashSet<_> = OK_ACCOUNT_IDS.iter().collect();
+        assert_eq!(uuids.len(), ids.len());
+    }
+
+    #[test]
+    fn test_label_start() {
+        let app = AccountIdRef::new_or_panic("x.app_v2.sub-account.alice.near");
+        let starts: Vec<_> = (0..6).map(|idx| app.label_start(idx)).collect();
+        assert_eq!(
+            starts,
+            [Some(0), Some(2), Some(9), Some(21), Some(27), None]
+        );
+        assert_eq!(app.label_start(usize::MAX), None);
+
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            for (idx, label) in account_id.labels().enumerate() {
+                let start = account_id.label_start(idx).unwrap();
+                assert_eq!(&account_id.as_str()[start..start + label.len()], label);
+            }
+            assert_eq!(account_id.label_start(account_id.num_labels()), None);
+        }
+    }
+
+    #[test]
+    fn test_registrar_levels() {
+        let account_id = AccountIdRef::new_or_panic("a.b.near");
+        assert_eq!(
+            account_id.registrar_levels().collect::<Vec<_>>(),
+            ["a.b.near", "b.near", "near"]
+        );
+
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            assert!(account_id
+                .registrar_levels()
+                .eq(account_id.ancestors_with_self()));
+        }
+    }
+
+    #[test]
+    fn test_num_labels() {
+        let cases = [
+            ("near", 1),
+            ("system", 1),
+            ("alice.near", 2),
+            ("app.alice.near", 3),
+            ("a.b.c.d.near", 5),
+            ("near.a", 2),
+            (
+                "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+                1,
+            ),
+            ("0xb794f5ea0ba39494ce839613fffba74279579268", 1),
+        ];
+        for (account_id, expected) in cases {
+            assert_eq!(
+                AccountIdRef::new(account_id).unwrap().num_labels(),
+                expected,
+                "{:?}",
+                account_id
+            );
+        }
+
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            if !account_id.is_system() {
+                assert_eq!(
+                    account_id.num_labels() == 1,
+                    account_id.is_top_level(),
+                    "{:?}",
+                    account_id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_depth() {
+        let cases = [
+            ("near", 1),
+            ("system", 1),
+            ("alice.near", 2),
+            ("app.alice.near", 3),
+            ("a.b.c.d.near", 5),
+            ("near.a", 1),
+            ("ab.c.d", 2),
+            (
+                "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+                1,
+            ),
+            ("0xb794f5ea0ba39494ce839613fffba74279579268", 1),
+        ];
+        for (account_id, expected) in cases {
+            assert_eq!(
+                AccountIdRef::new(account_id).unwrap().depth(),
+                expected,
+                "{:?}",
+                account_id
+            );
+        }
+
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            let depth = account_id.depth();
+            assert_eq!(
+                depth,
+                account_id.ancestors().count() + 1,
+                "{:?}",
+                account_id
+            );
+            assert!(depth <= account_id.num_labels(), "{:?}", account_id);
+            assert_eq!(
+                depth == account_id.num_labels(),
+                account_id.top_level_parent().num_labels() == 1,
+                "{:?}",
+                account_id
+            );
+            match account_id.parent() {
+                Some(parent) => assert_eq!(depth, parent.depth() + 1, "{:?}", account_id),
+                None => assert_eq!(depth, 1, "{:?}", account_id),
+            }
+            if account_id.is_top_level() {
+                assert_eq!(depth, 1, "{:?}", account_id);
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_implicit() {
+        let eth_implicit = [
+            "0xb794f5ea0ba39494ce839613fffba74279579268",
+            "0x0000000000000000000000000000000000000000",
+            "0xffffffffffffffffffffffffffffffffffffffff",
+        ];
+        for account_id in eth_implicit {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            assert!(account_id.is_eth_implicit(), "{:?}", account_id);
+            assert!(account_id.is_implicit(), "{:?}", account_id);
+            assert!(!account_id.is_near_implicit(), "{:?}", account_id);
+        }
+
+        // Valid Account IDs that merely look like ETH addresses
+        let named = [
+            "0xb794f5ea0ba39494ce839613fffba7427957926",
+            "0xb794f5ea0ba39494ce839613fffba7427957926g",
+            "1xb794f5ea0ba39494ce839613fffba74279579268",
+            "00b794f5ea0ba39494ce839613fffba74279579268",
+            "0x_794f5ea0ba39494ce839613fffba74279579268",
+            "0x",
+        ];
+        for account_id in named {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            assert!(!account_id.is_implicit(), "{:?}", account_id);
+        }
+
+        // Upper-case hex is not a valid Account ID at all
+        for account_id in [
+            "0xB794F5EA0BA39494CE839613FFFBA74279579268",
+            "0Xb794f5ea0ba39494ce839613fffba74279579268",
+        ] {
+            assert_eq!(
+                AccountIdRef::new(account_id).unwrap_err().kind(),
+                &ParseErrorKind::InvalidChar
+            );
+        }
+
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            assert_eq!(
+                account_id.is_implicit(),
+                account_id.is_near_implicit() || account_id.is_eth_implicit()
+            );
+        }
+    }
+
+    #[test]
+    fn test_require_implicit_key() {
+        let account_id = AccountIdRef::new_or_panic(
+            "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef",
+        );
+        let mut expected = [0; 32];
+        for chunk in expected.chunks_exact_mut(8) {
+            chunk.copy_from_slice(&[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef]);
+        }
+        assert_eq!(account_id.require_implicit_key(), Ok(expected));
+
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            assert_eq!(
+                account_id.require_implicit_key().is_ok(),
+                account_id.is_near_implicit(),
+                "{:?}",
+                account_id
+            );
+        }
+
+        let failures = [
+            (
+                "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcde",
+                ImplicitError::WrongLength(63),
+            ),
+            (
+                "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcd",
+                ImplicitError::WrongLength(62),
+            ),
+            ("abcdef", ImplicitError::WrongLength(6)),
+            (
+                "0123456789abcdef0123456789abcdefg123456789abcdef0123456789abcdef",
+                ImplicitError::InvalidHex(32, 'g'),
+            ),
+            (
+                "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdez",
+                ImplicitError::InvalidHex(63, 'z'),
+            ),
+            (
+                "0123456789abcdef0123456789abcdef.123456789abcdef0123456789abcdef",
+                ImplicitError::NotImplicit,
+            ),
+            ("alice.near", ImplicitError::NotImplicit),
+            ("system", ImplicitError::NotImplicit),
+            ("ab.cd", ImplicitError::NotImplicit),
+            (
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+                ImplicitError::NotImplicit,
+            ),
+        ];
+        for (account_id, expected) in failures {
+            assert_eq!(
+                AccountIdRef::new(account_id)
+                    .unwrap()
+                    .require_implicit_key(),
+                Err(expected),
+                "{:?}",
+                account_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_eth_implicit_helpers() {
+        let valid = &[
+            "0x0000000000000000000000000000000000000000",
+            "0xb794f5ea0ba39494ce839613fffba74279579268",
+            "0xffffffffffffffffffffffffffffffffffffffff",
+        ];
+        for account_id in valid {
+            let account_id = AccountIdRef::new(account_id)
+                .unwrap_or_else(|err| panic!("{:?} should be valid: {}", account_id, err));
+            assert!(account_id.is_eth_implicit(), "{:?}", account_id);
+            assert!(!account_id.is_near_implicit(), "{:?}", account_id);
+        }
+
+        // Not valid account IDs at all
+        let invalid = &[
+            "0xB794F5EA0BA39494CE839613FFFBA74279579268",
+            "0xb794f5ea0ba39494ce839613fffba7427957926A",
+            "0Xb794f5ea0ba39494ce839613fffba74279579268",
+        ];
+        for account_id in invalid {
+            assert!(
+                AccountIdRef::new(account_id).is_err(),
+                "{:?} should be invalid",
+                account_id
+            );
+        }
+
+        // Valid account IDs, but not ETH-implicit
+        let named = &[
+            "0x",
+            "0xb794f5ea0ba39494ce839613fffba7427957926",
+            "0xb794f5ea0ba39494ce839613fffba742795792680",
+            "0xb794f5ea0ba39494ce839613fffba74279579268.near",
+            "0xb794f5ea0ba39494ce839613-fffba7427957926",
+            "0xb794f5ea0ba39494ce839613_fffba7427957926",
+            "0xb794f5ea0ba39494ce839613fffba7427957926g",
+            "00b794f5ea0ba39494ce839613fffba74279579268",
+        ];
+        for account_id in named {
+            let account_id = AccountIdRef::new(account_id)
+                .unwrap_or_else(|err| panic!("{:?} should be valid: {}", account_id, err));
+            assert!(!account_id.is_eth_implicit(), "{:?}", account_id);
+            assert!(!account_id.is_near_implicit(), "{:?}", account_id);
+        }
+
+        let near_implicit = AccountIdRef::new_or_panic(
+            "0123456789012345678901234567890123456789012345678901234567890123",
+        );
+        assert!(near_implicit.is_near_implicit());
+        assert!(!near_implicit.is_eth_implicit());
+    }
+    #[test]
+    fn test_parent() {
+        let cases = &[
+            ("app.alice.near", Some("alice.near")),
+            ("alice.near", Some("near")),
+            ("a.b-a.ra", Some("b-a.ra")),
+            ("illia.cheapaccounts.near", Some("cheapaccounts.near")),
+            ("near", None),
+            ("system", None),
+            (
+                "0123456789012345678901234567890123456789012345678901234567890123",
+                None,
+            ),
+            ("0xb794f5ea0ba39494ce839613fffba74279579268", None),
+            // The parent would be too short to be a valid account ID
+            ("near.a", None),
+        ];
+        for (account_id, expected_parent) in cases {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            let parent = account_id.parent();
+            assert_eq!(
+                parent.map(AccountIdRef::as_str),
+                *expected_parent,
+                "Unexpected parent of {:?}",
+                account_id
+            );
+            if let Some(parent) = parent {
+                assert!(
+                    account_id.is_sub_account_of(parent),
+                    "{:?} is not a sub-account of its parent {:?}",
+                    account_id,
+                    parent
+                );
+            }
+        }
+
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            if let Some(parent) = account_id.parent() {
+                assert!(AccountIdRef::new(parent).is_ok());
+                assert!(account_id.is_sub_account_of(parent));
+            }
+        }
+    }
+
+    #[test]
+    fn test_join() {
+        let alice = AccountIdRef::new_or_panic("alice.near");
+        for child in ["app", "a", "1_4m_n0t-al1c3", "v2"] {
+            let account_id = alice.join(child).unwrap();
+            assert_eq!(account_id, format!("{}.{}", child, alice));
+            assert!(account_id.is_sub_account_of(alice));
+        }
+
+        for account_id in OK_ACCOUNT_IDS {
+            let parent = AccountIdRef::new(account_id).unwrap();
+            match parent.join("x") {
+                Ok(child) => assert!(child.is_sub_account_of(parent)),
+                Err(err) => assert_eq!(err, ParseAccountError::too_long(parent.len() + 2, 64)),
+            }
+        }
+
+        let invalid = |kind, idx, char| ParseAccountError {
+            kind,
+            char: Some((idx, char)),
+            len: None,
+        };
+        for (child, expected) in [
+            ("my.app", invalid(ParseErrorKind::InvalidChar, 2, '.')),
+            ("App", invalid(ParseErrorKind::InvalidChar, 0, 'A')),
+            ("my@app", invalid(ParseErrorKind::InvalidChar, 2, '@')),
+            ("-app", invalid(ParseErrorKind::RedundantSeparator, 0, '-')),
+            ("app_", invalid(ParseErrorKind::RedundantSeparator, 3, '_')),
+            ("a--b", invalid(ParseErrorKind::RedundantSeparator, 2, '-')),
+            ("a..b", invalid(ParseErrorKind::InvalidChar, 1, '.')),
+            ("a.", invalid(ParseErrorKind::InvalidChar, 1, '.')),
+            ("ƒelicia", invalid(ParseErrorKind::InvalidChar, 0, 'ƒ')),
+        ] {
+            assert_eq!(alice.join(child), Err(expected), "{:?}", child);
+        }
+        assert_eq!(alice.join("").unwrap_err(), ParseAccountError::too_short(0));
+
+        // Same errors as validating the label upfront
+        let longest = "a".repeat(AccountIdRef::MAX_LEN - alice.len() - 1);
+        let too_long = [format!("{}@", longest), format!("{}ab", longest)];
+        for child in BAD_ACCOUNT_IDS
+            .iter()
+            .copied()
+            .chain([longest.as_str(), &longest[1..], "a"])
+            .chain(too_long.iter().map(String::as_str))
+        {
+            assert_eq!(
+                alice.join(child).err(),
+                crate::validate_new_label(alice, child).err(),
+                "{:?}",
+                child
+            );
+        }
+
+        // The length is checked on the result
+        assert_eq!(alice.join(&longest).unwrap().len(), AccountIdRef::MAX_LEN);
+        assert_eq!(
+            alice.join(&format!("{}a", longest)),
+            Err(ParseAccountError::too_long(65, AccountIdRef::MAX_LEN))
+        );
+        assert_eq!(
+            alice.join(&format!("{}@", longest)).unwrap_err().kind(),
+            &ParseErrorKind::TooLong
+        );
+    }
+
+    #[test]
+    fn test_display_truncated() {
+        let alice = AccountIdRef::new_or_panic("alice.near");
+        for max in [10, 11, 64, usize::MAX] {
+            assert!(matches!(
+                alice.display_truncated(max),
+                Cow::Borrowed("alice.near")
+            ));
+        }
+        assert_eq!(alice.display_truncated(9), "alic…near");
+        assert_eq!(alice.display_truncated(8), "alic…ear");
+        assert_eq!(alice.display_truncated(3), "a…r");
+        assert_eq!(alice.display_truncated(2), "a…");
+
+        let implicit = AccountIdRef::new_or_panic(
+            "0000000000000000000000000000000000000000000000000000000000000000",
+        );
+        let truncated = implicit.display_truncated(12);
+        assert_eq!(truncated, "000000…00000");
+        assert_eq!(truncated.chars().count(), 12);
+
+        // Smaller than the ellipsis, or just as long
+        assert_eq!(alice.display_truncated(1), "…");
+        assert_eq!(alice.display_truncated(0), "");
+        assert_eq!(implicit.display_truncated(0), "");
+
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new_or_panic(account_id);
+            for max in 0..=account_id.len() + 1 {
+                let truncated = account_id.display_truncated(max);
+                assert_eq!(truncated.chars().count(), max.min(account_id.len()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_longest_valid_prefix() {
+        let longest = |input| AccountIdRef::longest_valid_prefix(input).map(AccountIdRef::as_str);
+
+        assert_eq!(longest("alice.near.extra@"), Some("alice.near"));
+        assert_eq!(longest("alice.near"), Some("alice.near"));
+        assert_eq!(longest("alice.near."), Some("alice.near"));
+        assert_eq!(longest("app.alice..near"), Some("app.alice"));
+        assert_eq!(longest("alice.a"), Some("alice.a"));
+        // Too short prefixes fall back to a valid shorter one (or none)
+        assert_eq!(longest("a.b"), Some("a.b"));
+        assert_eq!(longest("a."), None);
+        // Prefixes only end on label boundaries
+        assert_eq!(longest("alice@"), None);
+        assert_eq!(longest("@alice.near"), None);
+        assert_eq!(longest("Alice.near"), None);
+        assert_eq!(longest(""), None);
+
+        // Prefixes past the maximum length are skipped
+        let long = format!("{}.near.{}", "a".repeat(59), "b".repeat(10));
+        assert_eq!(longest(&long), Some(&long[..64]));
+        let long = format!("{}.near", "a".repeat(65));
+        assert_eq!(longest(&long), None);
+
+        for account_id in OK_ACCOUNT_IDS {
+            assert_eq!(longest(account_id), Some(account_id));
+        }
+    }
+
+    #[test]
+    fn test_split_once_label() {
+        let cases = [
+            ("app.alice.near", Some(("app", "alice.near"))),
+            ("alice.near", Some(("alice", "near"))),
+            ("a.near", Some(("a", "near"))),
+            ("near", None),
+            ("system", None),
+            ("near.a", None),
+            (
+                "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+                None,
+            ),
+            ("0xb794f5ea0ba39494ce839613fffba74279579268", None),
+        ];
+        for (account_id, expected) in cases {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            assert_eq!(
+                account_id
+                    .split_once_label()
+                    .map(|(label, rest)| (label, rest.as_str())),
+                expected,
+                "{:?}",
+                account_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_first_and_last_label() {
+        let cases = [
+            (
+                "app.alice.near",
+                ("app", Some("alice.near")),
+                (Some("app.alice"), "near"),
+            ),
+            (
+                "alice.near",
+                ("alice", Some("near")),
+                (Some("alice"), "near"),
+            ),
+            ("a.near", ("a", Some("near")), (None, "near")),
+            ("near.a", ("near.a", None), (Some("near"), "a")),
+            (
+                "x.y.z.near",
+                ("x", Some("y.z.near")),
+                (Some("x.y.z"), "near"),
+            ),
+            ("near", ("near", None), (None, "near")),
+            ("system", ("system", None), (None, "system")),
+            (
+                "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+                (
+                    "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+                    None,
+                ),
+                (
+                    None,
+                    "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+                ),
+            ),
+            (
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+                ("0xb794f5ea0ba39494ce839613fffba74279579268", None),
+                (None, "0xb794f5ea0ba39494ce839613fffba74279579268"),
+            ),
+        ];
+        for (account_id, (first, parent), (prefix, last)) in cases {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            let (label, rest) = account_id.split_first_label();
+            assert_eq!((label, rest.map(AccountIdRef::as_str)), (first, parent));
+            assert_eq!((label, rest), account_id.split_parent());
+            assert_eq!(rest, account_id.parent());
+
+            let (rest, label) = account_id.split_last_label();
+            assert_eq!((rest.map(AccountIdRef::as_str), label), (prefix, last));
+            match rest {
+                Some(rest) => {
+                    assert!(AccountIdRef::new(rest.as_str()).is_ok());
+                    assert_eq!(format!("{}.{}", rest, label), account_id.as_str());
+                }
+                None => assert!(account_id.as_str().ends_with(label)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_parent() {
+        let cases = &[
+            ("app.alice.near", ("app", Some("alice.near"))),
+            ("a.near", ("a", Some("near"))),
+            ("x.y.z.near", ("x", Some("y.z.near"))),
+            ("near", ("near", None)),
+            ("system", ("system", None)),
+            (
+                "0123456789012345678901234567890123456789012345678901234567890123",
+                (
+                    "0123456789012345678901234567890123456789012345678901234567890123",
+                    None,
+                ),
+            ),
+            ("near.a", ("near.a", None)),
+        ];
+        for (account_id, (expected_name, expected_parent)) in cases {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            let (name, parent) = account_id.split_parent();
+            assert_eq!(name, *expected_name);
+            assert_eq!(parent.map(AccountIdRef::as_str), *expected_parent);
+            assert_eq!(parent, account_id.parent());
+        }
+    }
+
     #[test]
     #[cfg(feature = "arbitrary")]
     fn test_arbitrary() {
@@ -0,0 +1,201 @@
+use alloc::collections::{btree_set, BTreeSet};
+
+use crate::{AccountId, AccountIdRef};
+
+/// A set of [`AccountId`]s that is aware of the account hierarchy.
+///
+/// On top of plain membership checks, it can tell whether an account or any
+/// of its ancestors is present in the set, which is handy for allow-lists where
+/// listing `alice.near` also covers `app.alice.near`.
+///
+/// ## Examples
+///
+/// ```
+/// use unc_account_id::{AccountIdRef, AccountIdSet};
+///
+/// let mut set = AccountIdSet::new();
+/// set.insert("alice.near".parse().unwrap());
+///
+/// let app = AccountIdRef::new_or_panic("app.alice.near");
+/// assert!(!set.contains(app));
+/// assert!(set.contains_ancestor_of(app));
+///
+/// // Only whole labels are matched
+/// assert!(!set.contains_ancestor_of(AccountIdRef::new_or_panic("malice.near")));
+/// ```
+#[derive(Eq, Hash, Clone, Debug, Default, PartialEq)]
+pub struct AccountIdSet(BTreeSet<AccountId>);
+
+impl AccountIdSet {
+    /// Creates an empty `AccountIdSet`.
+    pub fn new() -> Self {
+        Self(BTreeSet::new())
+    }
+
+    /// Adds an account to the set.
+    ///
+    /// Returns `true` if the account was not present in the set before.
+    pub fn insert(&mut self, account_id: AccountId) -> bool {
+        self.0.insert(account_id)
+    }
+
+    /// Removes an account from the set.
+    ///
+    /// Returns `true` if the account was present in the set.
+    pub fn remove(&mut self, account_id: &AccountIdRef) -> bool {
+        self.0.remove(account_id)
+    }
+
+    /// Returns `true` if the set contains exactly this account.
+    pub fn contains(&self, account_id: &AccountIdRef) -> bool {
+        self.0.contains(account_id)
+    }
+
+    /// Returns `true` if the set contains either this account or any of its ancestors.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::{AccountIdRef, AccountIdSet};
+    ///
+    /// let set: AccountIdSet = ["near".parse().unwrap()].into_iter().collect();
+    ///
+    /// assert!(set.contains_ancestor_of(AccountIdRef::new_or_panic("near")));
+    /// assert!(set.contains_ancestor_of(AccountIdRef::new_or_panic("app.alice.near")));
+    /// assert!(!set.contains_ancestor_of(AccountIdRef::new_or_panic("alice.testnet")));
+    /// ```
+    pub fn contains_ancestor_of(&self, account_id: &AccountIdRef) -> bool {
+        account_id
+            .ancestors_with_self()
+            .any(|ancestor| self.contains(ancestor))
+    }
+
+    /// Returns the number of accounts in the set.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the set contains no accounts.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the accounts in the set, in ascending order.
+    pub fn iter(&self) -> btree_set::Iter<'_, AccountId> {
+        self.0.iter()
+    }
+}
+
+impl FromIterator<AccountId> for AccountIdSet {
+    fn from_iter<I: IntoIterator<Item = AccountId>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Extend<AccountId> for AccountIdSet {
+    fn extend<I: IntoIterator<Item = AccountId>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+impl IntoIterator for AccountIdSet {
+    type Item = AccountId;
+    type IntoIter = btree_set::IntoIter<AccountId>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a AccountIdSet {
+    type Item = &'a AccountId;
+    type IntoIter = btree_set::Iter<'a, AccountId>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(account_ids: &[&str]) -> AccountIdSet {
+        account_ids.iter().map(|id| id.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_contains() {
+        let set = set(&["alice.near", "bob.testnet"]);
+
+        for account_id in ["alice.near", "bob.testnet"] {
+            assert!(
+                set.contains(AccountIdRef::new(account_id).unwrap()),
+                "{:?} should be in the set",
+                account_id
+            );
+        }
+
+        for account_id in ["near", "app.alice.near", "bob.near", "testnet"] {
+            assert!(
+                !set.contains(AccountIdRef::new(account_id).unwrap()),
+                "{:?} should not be in the set",
+                account_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_contains_ancestor_of() {
+        let set = set(&["alice.near", "testnet"]);
+
+        let covered = [
+            "alice.near",
+            "app.alice.near",
+            "x.app.alice.near",
+            "testnet",
+            "bob.testnet",
+            "app.bob.testnet",
+        ];
+        for account_id in covered {
+            assert!(
+                set.contains_ancestor_of(AccountIdRef::new(account_id).unwrap()),
+                "{:?} should be covered by the set",
+                account_id
+            );
+        }
+
+        let not_covered = [
+            "near",
+            "bob.near",
+            "malice.near",
+            "app.malice.near",
+            "alice.neart",
+            "alice-near",
+            "mytestnet",
+            "bob.mytestnet",
+            "0123456789012345678901234567890123456789012345678901234567890123",
+        ];
+        for account_id in not_covered {
+            assert!(
+                !set.contains_ancestor_of(AccountIdRef::new(account_id).unwrap()),
+                "{:?} should not be covered by the set",
+                account_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut set = AccountIdSet::new();
+        assert!(set.is_empty());
+
+        assert!(set.insert("alice.near".parse().unwrap()));
+        assert!(!set.insert("alice.near".parse().unwrap()));
+        assert_eq!(set.len(), 1);
+
+        assert!(set.remove(AccountIdRef::new_or_panic("alice.near")));
+        assert!(!set.remove(AccountIdRef::new_or_panic("alice.near")));
+        assert!(set.is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+//! Lowercase, unpadded base32 (RFC 4648 alphabet) used by URL tokens.
+
+use alloc::{string::String, vec::Vec};
+
+const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+pub fn encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() * 8).div_ceil(5));
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+
+    for &byte in bytes {
+        buffer = (buffer << 8) | u16::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[usize::from((buffer >> bits) & 0x1f)] as char);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        out.push(ALPHABET[usize::from((buffer << (5 - bits)) & 0x1f)] as char);
+    }
+
+    out
+}
+
+/// Decodes a token produced by [`encode`].
+///
+/// On failure, returns the index and value of the offending character.
+/// Non-canonical trailing characters are reported as offending too.
+pub fn decode(token: &str) -> Result<Vec<u8>, (usize, char)> {
+    let mut out = Vec::with_capacity(token.len() * 5 / 8);
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+    let mut last = None;
+
+    for (idx, c) in token.chars().enumerate() {
+        let value = match c {
+            'a'..='z' => c as u8 - b'a',
+            '2'..='7' => c as u8 - b'2' + 26,
+            _ => return Err((idx, c)),
+        };
+        last = Some((idx, c));
+
+        buffer = (buffer << 5) | u16::from(value);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    match last {
+        Some(last) if bits >= 5 || buffer != 0 => Err(last),
+        _ => Ok(out),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let corpus: &[&[u8]] = &[b"", b"a", b"ab", b"abc", b"abcd", b"abcde", b"abcdef"];
+        for bytes in corpus {
+            assert_eq!(decode(&encode(bytes)).as_deref(), Ok(*bytes));
+        }
+    }
+
+    #[test]
+    fn test_rfc4648_vectors() {
+        let vectors = [
+            ("f", "my"),
+            ("fo", "mzxq"),
+            ("foo", "mzxw6"),
+            ("foob", "mzxw6yq"),
+            ("fooba", "mzxw6ytb"),
+            ("foobar", "mzxw6ytboi"),
+        ];
+        for (input, output) in vectors {
+            assert_eq!(encode(input.as_bytes()), output);
+            assert_eq!(decode(output).unwrap(), input.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(decode("mZxq"), Err((1, 'Z')));
+        assert_eq!(decode("mzx1"), Err((3, '1')));
+        assert_eq!(decode("mzx="), Err((3, '=')));
+        // Trailing bits must be zero
+        assert_eq!(decode("mz"), Err((1, 'z')));
+        // A dangling character doesn't encode a whole byte
+        assert_eq!(decode("mzxqmz"), Err((5, 'z')));
+    }
+}
@@ -1,33 +1,72 @@
-use crate::AccountIdRef;
+use crate::{AccountIdRef, ParseAccountError};
 
 use super::AccountId;
 
-use std::io::{Read, Write};
+use alloc::{format, string::String};
 
+use borsh::io::{self, Read, Write};
 use borsh::{BorshDeserialize, BorshSerialize};
 
 impl BorshSerialize for AccountId {
-    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        self.0.serialize(writer)
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.as_str().serialize(writer)
     }
 }
 
 impl BorshSerialize for AccountIdRef {
-    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
         self.0.serialize(writer)
     }
 }
 
+// `AccountIdRef` has the same wire format as `AccountId`, so it shares its schema instead of
+// describing a distinct type.
+#[cfg(feature = "abi")]
+impl borsh::BorshSchema for AccountIdRef {
+    fn add_definitions_recursively(
+        definitions: &mut alloc::collections::BTreeMap<
+            borsh::schema::Declaration,
+            borsh::schema::Definition,
+        >,
+    ) {
+        AccountId::add_definitions_recursively(definitions)
+    }
+
+    fn declaration() -> borsh::schema::Declaration {
+        AccountId::declaration()
+    }
+}
+
 impl BorshDeserialize for AccountId {
-    fn deserialize_reader<R: Read>(rd: &mut R) -> std::io::Result<Self> {
-        let account_id = Box::<str>::deserialize_reader(rd)?;
-        crate::validation::validate(&account_id).map_err(|err| {
-            std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!("invalid value: \"{}\", {}", account_id, err),
+    fn deserialize_reader<R: Read>(rd: &mut R) -> io::Result<Self> {
+        let len = u32::deserialize_reader(rd)? as usize;
+        if len > crate::validation::MAX_LEN {
+            let err = ParseAccountError::too_long(len, crate::validation::MAX_LEN);
+            // The value itself isn't read, so there is nothing to show.
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid value: {}", err),
+            ));
+        }
+
+        // Account IDs are short, so they are read onto the stack and validated there,
+        // leaving a single allocation for valid ones.
+        let mut buf = [0; crate::validation::MAX_LEN];
+        let buf = &mut buf[..len];
+        rd.read_exact(buf)?;
+
+        // A single pass, which also reports bytes that aren't UTF-8 at their offset.
+        let account_id = crate::validation::validate_bytes(buf).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "invalid value: \"{}\", {}",
+                    String::from_utf8_lossy(buf),
+                    err
+                ),
             )
         })?;
-        Ok(Self(account_id))
+        Ok(Self(account_id.into()))
     }
 }
 
@@ -36,7 +75,7 @@ mod tests {
     use borsh::BorshDeserialize as _;
 
     use crate::test_data::{BAD_ACCOUNT_IDS, OK_ACCOUNT_IDS};
-    use crate::AccountId;
+    use crate::{AccountId, AccountIdRef};
 
     #[test]
     fn test_is_valid_account_id() {
@@ -71,6 +110,104 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_account_id_ref() {
+        for account_id in OK_ACCOUNT_IDS {
+            let owned: AccountId = account_id.parse().unwrap();
+            let borrowed: &AccountIdRef = &owned;
+            let boxed: Box<AccountIdRef> = owned.clone().into();
+
+            let serialized = borsh::to_vec(&owned).unwrap();
+            assert_eq!(borsh::to_vec(borrowed).unwrap(), serialized);
+            assert_eq!(borsh::to_vec(&boxed).unwrap(), serialized);
+
+            let deserialized = Box::<AccountIdRef>::try_from_slice(&serialized).unwrap();
+            assert_eq!(deserialized, boxed);
+        }
+
+        for account_id in BAD_ACCOUNT_IDS {
+            let str_serialized_account_id = borsh::to_vec(account_id).unwrap();
+            assert!(Box::<AccountIdRef>::try_from_slice(&str_serialized_account_id).is_err());
+        }
+    }
+
+    #[test]
+    fn test_deserialize_errors() {
+        let err = AccountId::try_from_slice(&borsh::to_vec("alice..near").unwrap()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value: \"alice..near\", the Account ID has a redundant separator '.' at index 6"
+        );
+
+        // Too long Account IDs are rejected from the length prefix alone
+        let mut too_long = 65u32.to_le_bytes().to_vec();
+        let err = AccountId::deserialize(&mut too_long.as_slice()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid value: the Account ID is 65 bytes long, but the maximum is 64"
+        );
+        too_long.extend([b'a'; 65]);
+        assert!(AccountId::try_from_slice(&too_long).is_err());
+
+        let err = AccountId::try_from_slice(&u32::MAX.to_le_bytes()).unwrap_err();
+        assert_eq!(err.kind(), borsh::io::ErrorKind::InvalidData);
+
+        // Truncated input
+        let serialized = borsh::to_vec("alice.near").unwrap();
+        assert!(AccountId::try_from_slice(&serialized[..serialized.len() - 1]).is_err());
+        assert!(AccountId::try_from_slice(&serialized[..3]).is_err());
+
+        // Trailing input
+        let mut trailing = serialized.clone();
+        trailing.push(0);
+        assert!(AccountId::try_from_slice(&trailing).is_err());
+        assert_eq!(
+            AccountId::deserialize(&mut trailing.as_slice()).unwrap(),
+            "alice.near"
+        );
+
+        let invalid_utf8 = [3, 0, 0, 0, b'a', 0xc3, 0x28];
+        let err = AccountId::try_from_slice(&invalid_utf8).unwrap_err();
+        assert_eq!(err.kind(), borsh::io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "invalid value: \"a\u{fffd}(\", the Account ID is not valid UTF-8 at index 1"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "abi")]
+    fn test_schema() {
+        use borsh::BorshSchema;
+
+        assert_eq!(AccountIdRef::declaration(), AccountId::declaration());
+        assert_eq!(
+            borsh::schema_container_of::<Box<AccountIdRef>>(),
+            borsh::schema_container_of::<AccountId>()
+        );
+
+        #[derive(BorshSchema)]
+        #[allow(dead_code)]
+        struct Transfer {
+            sender: AccountId,
+            receiver: Box<AccountIdRef>,
+        }
+
+        let container = borsh::schema_container_of::<Transfer>();
+        let Some(borsh::schema::Definition::Struct {
+            fields: borsh::schema::Fields::NamedFields(fields),
+        }) = container.get_definition("Transfer")
+        else {
+            panic!("unexpected schema: {:?}", container);
+        };
+        assert_eq!(fields[0].1, fields[1].1);
+        assert_eq!(
+            container.get_definition(&fields[0].1),
+            borsh::schema_container_of::<AccountId>().get_definition("AccountId")
+        );
+        assert!(container.get_definition("AccountIdRef").is_none());
+    }
+
     #[test]
     fn fuzz() {
         bolero::check!().for_each(|input: &[u8]| {
@@ -0,0 +1,219 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{AccountId, AccountIdRef, ParseAccountError};
+
+/// A builder for sub-accounts of an existing account.
+///
+/// Each call to [`prefix`](AccountIdBuilder::prefix) adds a label in front of the Account ID
+/// built so far, and [`build`](AccountIdBuilder::build) validates the result as a whole.
+///
+/// ## Examples
+///
+/// ```
+/// use unc_account_id::{AccountIdBuilder, AccountIdRef, ParseErrorKind};
+///
+/// let alice = AccountIdRef::new_or_panic("alice.near");
+///
+/// let app = AccountIdBuilder::from_parent(alice).prefix("app").build().unwrap();
+/// assert_eq!(app, "app.alice.near");
+///
+/// let v2 = AccountIdBuilder::from_parent(alice).prefix("app").prefix("v2").build().unwrap();
+/// assert_eq!(v2, "v2.app.alice.near");
+///
+/// // Labels can't contain `.`
+/// let err = AccountIdBuilder::from_parent(alice).prefix("my.app").build().unwrap_err();
+/// assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+/// ```
+#[derive(Clone, Debug)]
+pub struct AccountIdBuilder<'a> {
+    parent: &'a AccountIdRef,
+    // In the order they were added, i.e. the last one ends up leftmost.
+    labels: Vec<String>,
+}
+
+impl<'a> AccountIdBuilder<'a> {
+    /// Starts building a sub-account of `parent`.
+    pub fn from_parent(parent: &'a AccountIdRef) -> Self {
+        Self {
+            parent,
+            labels: Vec::new(),
+        }
+    }
+
+    /// Adds a label in front of the Account ID built so far.
+    ///
+    /// The label is not validated until [`build`](AccountIdBuilder::build) is called.
+    pub fn prefix(mut self, label: &str) -> Self {
+        self.labels.push(label.into());
+        self
+    }
+
+    /// Builds the Account ID.
+    ///
+    /// Each label is validated like [`validate_new_label`](crate::validate_new_label) does, so a label
+    /// containing a `.` is rejected. The error is [`TooLong`](crate::ParseErrorKind::TooLong) if the result
+    /// would be longer than [`AccountId::MAX_LEN`], otherwise that of the leftmost invalid label,
+    /// pointing at the offending character in the resulting Account ID.
+    pub fn build(&self) -> Result<AccountId, ParseAccountError> {
+        crate::validation::join_labels(self.labels.iter().rev(), Some(self.parent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseErrorKind;
+
+    fn build(parent: &str, labels: &[&str]) -> Result<AccountId, ParseAccountError> {
+        labels
+            .iter()
+            .fold(
+                AccountIdBuilder::from_parent(AccountIdRef::new(parent).unwrap()),
+                |builder, label| builder.prefix(label),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_build() {
+        let cases: &[(&str, &[&str], &str)] = &[
+            ("near", &[], "near"),
+            ("near", &["alice"], "alice.near"),
+            ("alice.near", &["app"], "app.alice.near"),
+            ("alice.near", &["app", "v2"], "v2.app.alice.near"),
+            ("near", &["1_4m_n0t-al1c3"], "1_4m_n0t-al1c3.near"),
+            ("near", &["a"], "a.near"),
+        ];
+        for (parent, labels, expected) in cases {
+            let account_id = build(parent, labels).unwrap();
+            assert_eq!(account_id, *expected);
+            if !labels.is_empty() {
+                assert!(account_id.is_descendant_of(AccountIdRef::new_or_panic(parent)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_build_invalid() {
+        let cases: &[(&str, &[&str], ParseAccountError)] = &[
+            (
+                "near",
+                &["my.app"],
+                ParseAccountError {
+                    kind: ParseErrorKind::InvalidChar,
+                    char: Some((2, '.')),
+                    len: None,
+                },
+            ),
+            (
+                "alice.near",
+                &["app", "my.v2"],
+                ParseAccountError {
+                    kind: ParseErrorKind::InvalidChar,
+                    char: Some((2, '.')),
+                    len: None,
+                },
+            ),
+            (
+                "alice.near",
+                &["my.app", "v2"],
+                ParseAccountError {
+                    kind: ParseErrorKind::InvalidChar,
+                    char: Some((5, '.')),
+                    len: None,
+                },
+            ),
+            (
+                "near",
+                &["Alice"],
+                ParseAccountError {
+                    kind: ParseErrorKind::InvalidChar,
+                    char: Some((0, 'A')),
+                    len: None,
+                },
+            ),
+            (
+                "near",
+                &["ali ce"],
+                ParseAccountError {
+                    kind: ParseErrorKind::InvalidChar,
+                    char: Some((3, ' ')),
+                    len: None,
+                },
+            ),
+            ("near", &[""], ParseAccountError::too_short(0)),
+            (
+                "near",
+                &["-alice"],
+                ParseAccountError {
+                    kind: ParseErrorKind::RedundantSeparator,
+                    char: Some((0, '-')),
+                    len: None,
+                },
+            ),
+            (
+                "near",
+                &["alice_"],
+                ParseAccountError {
+                    kind: ParseErrorKind::RedundantSeparator,
+                    char: Some((5, '_')),
+                    len: None,
+                },
+            ),
+            (
+                "near",
+                &["alice..bob"],
+                ParseAccountError {
+                    kind: ParseErrorKind::InvalidChar,
+                    char: Some((5, '.')),
+                    len: None,
+                },
+            ),
+            (
+                "near",
+                &["0123456789012345678901234567890123456789012345678901234567890"],
+                ParseAccountError::too_long(66, AccountId::MAX_LEN),
+            ),
+            (
+                "alice.near",
+                &["012345678901234567890123456789", "01234567890123456789012"],
+                ParseAccountError::too_long(65, AccountId::MAX_LEN),
+            ),
+        ];
+        for (parent, labels, expected) in cases {
+            assert_eq!(
+                build(parent, labels).as_ref(),
+                Err(expected),
+                "{:?} {:?}",
+                parent,
+                labels
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_matches_validate_new_label() {
+        let alice = AccountIdRef::new_or_panic("alice.near");
+        for label in ["a..b", "a.", "", "-a", "a_", "A", "ƒ", "my@app"] {
+            assert_eq!(
+                AccountIdBuilder::from_parent(alice)
+                    .prefix(label)
+                    .build()
+                    .err(),
+                crate::validate_new_label(alice, label).err(),
+                "{:?}",
+                label
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_max_len() {
+        let label = "012345678901234567890123456789012345678901234567890123456789";
+        assert_eq!(
+            build("near", &[&label[..59]]).unwrap().len(),
+            AccountId::MAX_LEN
+        );
+        assert!(build("near", &[label]).is_err());
+    }
+}
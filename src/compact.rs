@@ -0,0 +1,436 @@
+use alloc::borrow::{Cow, ToOwned};
+use core::{cmp::Ordering, fmt, hash, str::FromStr};
+
+use crate::{AccountId, AccountIdRef, ParseAccountError};
+
+/// An [`AccountId`] that keeps implicit accounts as the raw bytes their hex encodes.
+///
+/// NEAR-implicit accounts take 32 bytes instead of 64 hex characters on the heap, and ETH-implicit ones
+/// 20 bytes, with nothing allocated for either. Named accounts are kept as an `AccountId`.
+/// On 64-bit targets, a `CompactAccountId` is 40 bytes, while an `AccountId` is 16 bytes plus its heap
+/// allocation: 64 bytes and the allocator's bookkeeping for a NEAR-implicit account.
+///
+/// It behaves like the Account ID it holds: comparisons, ordering, hashing, [`Display`](fmt::Display)
+/// and serialization all go through the string form, so a `CompactAccountId` can replace an `AccountId`
+/// in existing data without changing how it sorts or what ends up on the wire.
+///
+/// ## Examples
+///
+/// ```
+/// use unc_account_id::{AccountId, CompactAccountId};
+///
+/// let implicit: CompactAccountId =
+///     "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de".parse().unwrap();
+/// assert!(matches!(implicit, CompactAccountId::NearImplicit(key) if key[..2] == [0x98, 0x79]));
+///
+/// let alice: CompactAccountId = "alice.near".parse().unwrap();
+/// assert!(matches!(alice, CompactAccountId::Named(_)));
+///
+/// // Implicit accounts sort like their hex
+/// assert!(implicit < alice);
+/// assert_eq!(
+///     implicit.to_string(),
+///     "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de"
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub enum CompactAccountId {
+    /// Any Account ID, usually a named account.
+    Named(AccountId),
+    /// A NEAR-implicit account, as the 32 bytes of its public key.
+    NearImplicit([u8; 32]),
+    /// An ETH-implicit account, as the 20 bytes of its address.
+    EthImplicit([u8; 20]),
+}
+
+impl CompactAccountId {
+    /// Returns the Account ID, borrowing it for named accounts, and writing out the hex otherwise.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use unc_account_id::CompactAccountId;
+    ///
+    /// let alice: CompactAccountId = "alice.near".parse().unwrap();
+    /// assert!(matches!(alice.as_account_id(), Cow::Borrowed(_)));
+    ///
+    /// let eth: CompactAccountId = "0xb794f5ea0ba39494ce839613fffba74279579268".parse().unwrap();
+    /// assert_eq!(*eth.as_account_id(), "0xb794f5ea0ba39494ce839613fffba74279579268");
+    /// ```
+    pub fn as_account_id(&self) -> Cow<'_, AccountIdRef> {
+        match self {
+            Self::Named(account_id) => Cow::Borrowed(account_id),
+            _ => Cow::Owned(self.to_account_id()),
+        }
+    }
+
+    /// Returns the Account ID as an owned [`AccountId`].
+    pub fn to_account_id(&self) -> AccountId {
+        match self {
+            Self::Named(account_id) => account_id.clone(),
+            _ => self.with_str(|account_id| AccountId(account_id.into())),
+        }
+    }
+
+    /// Calls `f` with the string form of the Account ID, written out on the stack for implicit accounts.
+    fn with_str<T>(&self, f: impl FnOnce(&str) -> T) -> T {
+        let mut buf = [0; AccountId::MAX_LEN];
+        let len = match self {
+            Self::Named(account_id) => return f(account_id.as_str()),
+            Self::NearImplicit(key) => encode_hex(key, &mut buf),
+            Self::EthImplicit(address) => {
+                buf[..2].copy_from_slice(b"0x");
+                2 + encode_hex(address, &mut buf[2..])
+            }
+        };
+        f(core::str::from_utf8(&buf[..len]).expect("hex digits are ASCII"))
+    }
+
+    fn from_ref(account_id: &AccountIdRef) -> Option<Self> {
+        if account_id.is_near_implicit() {
+            Some(Self::NearImplicit(decode_hex(account_id.as_bytes())))
+        } else if account_id.is_eth_implicit() {
+            Some(Self::EthImplicit(decode_hex(&account_id.as_bytes()[2..])))
+        } else {
+            None
+        }
+    }
+}
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Writes the lower-case hex of `bytes` to the start of `buf`, returning the number of digits written.
+fn encode_hex(bytes: &[u8], buf: &mut [u8]) -> usize {
+    for (byte, digits) in bytes.iter().zip(buf.chunks_exact_mut(2)) {
+        digits[0] = HEX_DIGITS[usize::from(byte >> 4)];
+        digits[1] = HEX_DIGITS[usize::from(byte & 0xf)];
+    }
+    2 * bytes.len()
+}
+
+/// Decodes `2 * N` lower-case hex digits, already checked by `is_near_implicit` or `is_eth_implicit`.
+fn decode_hex<const N: usize>(hex: &[u8]) -> [u8; N] {
+    fn hex_value(digit: u8) -> u8 {
+        match digit {
+            b'0'..=b'9' => digit - b'0',
+            _ => digit - b'a' + 10,
+        }
+    }
+
+    let mut bytes = [0; N];
+    for (byte, digits) in bytes.iter_mut().zip(hex.chunks_exact(2)) {
+        *byte = (hex_value(digits[0]) << 4) | hex_value(digits[1]);
+    }
+    bytes
+}
+
+impl From<AccountId> for CompactAccountId {
+    fn from(account_id: AccountId) -> Self {
+        match Self::from_ref(&account_id) {
+            Some(compact) => compact,
+            None => Self::Named(account_id),
+        }
+    }
+}
+
+impl From<&AccountIdRef> for CompactAccountId {
+    fn from(account_id: &AccountIdRef) -> Self {
+        Self::from_ref(account_id).unwrap_or_else(|| Self::Named(account_id.to_owned()))
+    }
+}
+
+impl From<CompactAccountId> for AccountId {
+    fn from(account_id: CompactAccountId) -> Self {
+        match account_id {
+            CompactAccountId::Named(account_id) => account_id,
+            account_id => account_id.to_account_id(),
+        }
+    }
+}
+
+impl TryFrom<&str> for CompactAccountId {
+    type Error = ParseAccountError;
+
+    fn try_from(account_id: &str) -> Result<Self, Self::Error> {
+        AccountIdRef::new(account_id).map(Self::from)
+    }
+}
+
+impl FromStr for CompactAccountId {
+    type Err = ParseAccountError;
+
+    fn from_str(account_id: &str) -> Result<Self, Self::Err> {
+        Self::try_from(account_id)
+    }
+}
+
+impl fmt::Display for CompactAccountId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.with_str(|account_id| fmt::Display::fmt(account_id, f))
+    }
+}
+
+impl PartialEq for CompactAccountId {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::NearImplicit(a), Self::NearImplicit(b)) => a == b,
+            (Self::EthImplicit(a), Self::EthImplicit(b)) => a == b,
+            _ => self.cmp(other) == Ordering::Equal,
+        }
+    }
+}
+
+impl Eq for CompactAccountId {}
+
+impl PartialOrd for CompactAccountId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Hex digits sort like the bytes they encode, so implicit accounts of the same kind compare by bytes.
+impl Ord for CompactAccountId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::NearImplicit(a), Self::NearImplicit(b)) => a.cmp(b),
+            (Self::EthImplicit(a), Self::EthImplicit(b)) => a.cmp(b),
+            _ => self.with_str(|a| other.with_str(|b| a.cmp(b))),
+        }
+    }
+}
+
+impl hash::Hash for CompactAccountId {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.with_str(|account_id| account_id.hash(state))
+    }
+}
+
+impl PartialEq<AccountId> for CompactAccountId {
+    fn eq(&self, other: &AccountId) -> bool {
+        self.with_str(|account_id| account_id == other.as_str())
+    }
+}
+
+impl PartialEq<CompactAccountId> for AccountId {
+    fn eq(&self, other: &CompactAccountId) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<AccountIdRef> for CompactAccountId {
+    fn eq(&self, other: &AccountIdRef) -> bool {
+        self.with_str(|account_id| account_id == other.as_str())
+    }
+}
+
+impl PartialEq<CompactAccountId> for AccountIdRef {
+    fn eq(&self, other: &CompactAccountId) -> bool {
+        other == self
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CompactAccountId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.with_str(|account_id| AccountIdRef::new_unvalidated(account_id).serialize(serializer))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CompactAccountId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        AccountId::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for CompactAccountId {
+    fn serialize<W: borsh::io::Write>(&self, writer: &mut W) -> borsh::io::Result<()> {
+        self.with_str(|account_id| account_id.serialize(writer))
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for CompactAccountId {
+    fn deserialize_reader<R: borsh::io::Read>(rd: &mut R) -> borsh::io::Result<Self> {
+        AccountId::deserialize_reader(rd).map(Self::from)
+    }
+}
+
+#[cfg(feature = "abi")]
+impl borsh::BorshSchema for CompactAccountId {
+    fn add_definitions_recursively(
+        definitions: &mut alloc::collections::BTreeMap<
+            borsh::schema::Declaration,
+            borsh::schema::Definition,
+        >,
+    ) {
+        AccountId::add_definitions_recursively(definitions)
+    }
+
+    fn declaration() -> borsh::schema::Declaration {
+        AccountId::declaration()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeSet, HashSet};
+
+    use super::*;
+    use crate::test_data::{BAD_ACCOUNT_IDS, OK_ACCOUNT_IDS};
+
+    const NEAR_IMPLICIT: &str = "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de";
+    const ETH_IMPLICIT: &str = "0xb794f5ea0ba39494ce839613fffba74279579268";
+
+    fn account_ids() -> Vec<&'static str> {
+        let mut account_ids = OK_ACCOUNT_IDS.to_vec();
+        account_ids.extend([
+            NEAR_IMPLICIT,
+            ETH_IMPLICIT,
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "0x0000000000000000000000000000000000000000",
+            "0xffffffffffffffffffffffffffffffffffffffff",
+            // Close to implicit, but not quite
+            "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6d",
+            "0xb794f5ea0ba39494ce839613fffba7427957926",
+            "0xb794f5ea0ba39494ce839613fffba74279579268.near",
+        ]);
+        account_ids
+    }
+
+    #[test]
+    fn test_variants() {
+        let near = CompactAccountId::try_from(NEAR_IMPLICIT).unwrap();
+        let key = AccountIdRef::new_or_panic(NEAR_IMPLICIT)
+            .require_implicit_key()
+            .unwrap();
+        assert!(matches!(near, CompactAccountId::NearImplicit(k) if k == key));
+
+        let eth = CompactAccountId::try_from(ETH_IMPLICIT).unwrap();
+        assert!(matches!(
+            eth,
+            CompactAccountId::EthImplicit([0xb7, 0x94, .., 0x92, 0x68])
+        ));
+
+        for account_id in [
+            "alice.near",
+            "0xb794f5ea0ba39494ce839613fffba74279579268.near",
+        ] {
+            let compact = CompactAccountId::try_from(account_id).unwrap();
+            assert!(matches!(compact, CompactAccountId::Named(_)));
+        }
+
+        for account_id in BAD_ACCOUNT_IDS {
+            assert_eq!(
+                CompactAccountId::try_from(account_id).unwrap_err(),
+                AccountId::validate(account_id).unwrap_err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for account_id in account_ids() {
+            let parsed: AccountId = account_id.parse().unwrap();
+            let compact = CompactAccountId::from(parsed.clone());
+            assert_eq!(compact, CompactAccountId::from(&*parsed));
+            assert_eq!(compact.to_string(), account_id);
+            assert_eq!(compact.as_account_id(), *parsed);
+            assert_eq!(compact.to_account_id(), parsed);
+            assert_eq!(compact, parsed);
+            assert_eq!(*parsed, compact);
+            assert_eq!(AccountId::from(compact), parsed);
+        }
+    }
+
+    #[test]
+    fn test_ordering() {
+        let account_ids = account_ids();
+        let compact: Vec<CompactAccountId> =
+            account_ids.iter().map(|id| id.parse().unwrap()).collect();
+
+        for (a, compact_a) in account_ids.iter().zip(&compact) {
+            for (b, compact_b) in account_ids.iter().zip(&compact) {
+                assert_eq!(compact_a.cmp(compact_b), a.cmp(b), "{} vs {}", a, b);
+                assert_eq!(compact_a == compact_b, a == b, "{} vs {}", a, b);
+            }
+        }
+
+        let sorted: Vec<String> = compact
+            .iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(ToString::to_string)
+            .collect();
+        let mut expected = account_ids.clone();
+        expected.sort_unstable();
+        expected.dedup();
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    fn test_uncompacted_named() {
+        // An implicit account held as `Named` still behaves like the compact form
+        let named = CompactAccountId::Named(NEAR_IMPLICIT.parse().unwrap());
+        let compact = CompactAccountId::try_from(NEAR_IMPLICIT).unwrap();
+        assert_eq!(named, compact);
+        assert_eq!(named.cmp(&compact), Ordering::Equal);
+
+        let set: HashSet<_> = [named, compact].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_size() {
+        if cfg!(all(target_pointer_width = "64", not(feature = "inline"))) {
+            assert_eq!(core::mem::size_of::<CompactAccountId>(), 40);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        for account_id in account_ids() {
+            let parsed: AccountId = account_id.parse().unwrap();
+            let compact = CompactAccountId::from(parsed.clone());
+
+            let json = serde_json::to_string(&compact).unwrap();
+            assert_eq!(json, serde_json::to_string(&parsed).unwrap());
+            assert_eq!(
+                serde_json::from_str::<CompactAccountId>(&json).unwrap(),
+                compact
+            );
+
+            let bytes = bincode::serialize(&compact).unwrap();
+            assert_eq!(bytes, bincode::serialize(&parsed).unwrap());
+            assert_eq!(
+                bincode::deserialize::<CompactAccountId>(&bytes).unwrap(),
+                compact
+            );
+        }
+
+        assert!(serde_json::from_str::<CompactAccountId>(r#""Alice.near""#).is_err());
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_borsh() {
+        for account_id in account_ids() {
+            let parsed: AccountId = account_id.parse().unwrap();
+            let compact = CompactAccountId::from(parsed.clone());
+
+            let bytes = borsh::to_vec(&compact).unwrap();
+            assert_eq!(bytes, borsh::to_vec(&parsed).unwrap());
+            assert_eq!(
+                borsh::from_slice::<CompactAccountId>(&bytes).unwrap(),
+                compact
+            );
+        }
+
+        let bytes = borsh::to_vec("Alice.near").unwrap();
+        assert!(borsh::from_slice::<CompactAccountId>(&bytes).is_err());
+    }
+}
@@ -0,0 +1,110 @@
+use alloc::string::String;
+
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql};
+use diesel::serialize::{self, Output, ToSql};
+use diesel::sql_types::Text;
+
+use crate::AccountId;
+
+// Account IDs are stored as text. `AsExpression` and `FromSqlRow` are derived on `AccountId` itself,
+// so that it can be used in `Insertable` and `Queryable` structs.
+
+impl<DB: Backend> ToSql<Text, DB> for AccountId
+where
+    str: ToSql<Text, DB>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        self.as_str().to_sql(out)
+    }
+}
+
+/// Stored values are validated, and rejected with a [`ParseAccountError`](crate::ParseAccountError) if malformed.
+impl<DB: Backend> FromSql<Text, DB> for AccountId
+where
+    String: FromSql<Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        let account_id = String::from_sql(bytes)?;
+        Ok(AccountId::try_from(account_id)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "diesel-sqlite")]
+    #[test]
+    fn test_sqlite() {
+        use diesel::prelude::*;
+
+        use crate::{AccountId, ParseAccountError, ParseErrorKind};
+
+        diesel::table! {
+            accounts (id) {
+                id -> Integer,
+                account_id -> Text,
+            }
+        }
+
+        #[derive(Debug, PartialEq, Queryable, Insertable)]
+        #[diesel(table_name = accounts)]
+        struct Account {
+            id: i32,
+            account_id: AccountId,
+        }
+
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        diesel::sql_query(
+            "CREATE TABLE accounts (id INTEGER PRIMARY KEY, account_id TEXT NOT NULL)",
+        )
+        .execute(&mut conn)
+        .unwrap();
+
+        let alice: AccountId = "alice.near".parse().unwrap();
+        let inserted = [
+            Account {
+                id: 1,
+                account_id: alice.clone(),
+            },
+            Account {
+                id: 2,
+                account_id: "bob.near".parse().unwrap(),
+            },
+        ];
+        diesel::insert_into(accounts::table)
+            .values(&inserted[..])
+            .execute(&mut conn)
+            .unwrap();
+
+        let selected: Vec<Account> = accounts::table.order(accounts::id).load(&mut conn).unwrap();
+        assert_eq!(selected, inserted);
+
+        let account_id: AccountId = accounts::table
+            .select(accounts::account_id)
+            .filter(accounts::account_id.eq(&alice))
+            .first(&mut conn)
+            .unwrap();
+        assert_eq!(account_id, alice);
+
+        // Malformed stored values fail to deserialize
+        diesel::sql_query("INSERT INTO accounts (id, account_id) VALUES (3, 'Alice.near')")
+            .execute(&mut conn)
+            .unwrap();
+        let err = accounts::table
+            .select(accounts::account_id)
+            .filter(accounts::id.eq(3))
+            .first::<AccountId>(&mut conn)
+            .unwrap_err();
+        match err {
+            diesel::result::Error::DeserializationError(source) => {
+                let source = source
+                    .downcast_ref::<diesel::result::DeserializeFieldError>()
+                    .unwrap();
+                assert_eq!(source.field_name.as_deref(), Some("account_id"));
+                let err = source.error.downcast_ref::<ParseAccountError>().unwrap();
+                assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+            }
+            err => panic!("unexpected error: {}", err),
+        }
+    }
+}
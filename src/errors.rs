@@ -1,26 +1,131 @@
-use std::fmt;
-use std::fmt::Write;
+use alloc::string::ToString;
+use core::fmt;
+use core::fmt::Write;
+use core::ops::RangeInclusive;
+
+use crate::AccountIdRef;
 
 /// An error which can be returned when parsing a NEAR Account ID.
 #[derive(Eq, Clone, Debug, PartialEq)]
 pub struct ParseAccountError {
     pub(crate) kind: ParseErrorKind,
     pub(crate) char: Option<(usize, char)>,
+    /// The actual length and the maximum allowed length, for length violations.
+    pub(crate) len: Option<(usize, usize)>,
 }
 
 impl ParseAccountError {
+    pub(crate) const fn too_short(actual: usize) -> Self {
+        Self {
+            kind: ParseErrorKind::TooShort,
+            char: None,
+            len: Some((actual, AccountIdRef::MAX_LEN)),
+        }
+    }
+
+    pub(crate) const fn too_long(actual: usize, max: usize) -> Self {
+        Self {
+            kind: ParseErrorKind::TooLong,
+            char: None,
+            len: Some((actual, max)),
+        }
+    }
+
     /// Returns the specific cause why parsing the Account ID failed.
     pub fn kind(&self) -> &ParseErrorKind {
         &self.kind
     }
+
+    /// Returns the length of the rejected Account ID, if parsing failed because of its length.
+    ///
+    /// This is `Some` for [`ParseErrorKind::TooShort`] and [`ParseErrorKind::TooLong`] errors.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountId;
+    ///
+    /// let err = "a".parse::<AccountId>().unwrap_err();
+    /// assert_eq!(err.actual_len(), Some(1));
+    /// assert_eq!(err.expected_range(), Some(2..=64));
+    ///
+    /// let err = "alice..near".parse::<AccountId>().unwrap_err();
+    /// assert_eq!(err.actual_len(), None);
+    /// ```
+    pub fn actual_len(&self) -> Option<usize> {
+        self.len.map(|(actual, _)| actual)
+    }
+
+    /// Returns the range of lengths that would have been accepted, if parsing failed because of
+    /// the length of the Account ID.
+    ///
+    /// This is usually [`AccountId::MIN_LEN`](crate::AccountId::MIN_LEN)`..=`[`AccountId::MAX_LEN`](crate::AccountId::MAX_LEN),
+    /// but can be narrower for tighter bounds such as `LenBounded`.
+    pub fn expected_range(&self) -> Option<RangeInclusive<usize>> {
+        self.len.map(|(_, max)| AccountIdRef::MIN_LEN..=max)
+    }
+
+    /// Returns the byte offset of the offending character or byte, if parsing failed because of one.
+    ///
+    /// This is `None` for length violations. Also see [`ParseAccountError::offending_char`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use unc_account_id::AccountId;
+    ///
+    /// let err = "Emily.near".parse::<AccountId>().unwrap_err();
+    /// assert_eq!(err.position(), Some(0));
+    /// assert_eq!(err.offending_char(), Some('E'));
+    /// assert_eq!(err.to_string(), "the Account ID contains an invalid character 'E' at index 0");
+    ///
+    /// let err = AccountId::from_utf8(b"alice\xff.near".to_vec()).unwrap_err();
+    /// assert_eq!(err.position(), Some(5));
+    /// assert_eq!(err.offending_char(), None);
+    /// ```
+    pub fn position(&self) -> Option<usize> {
+        self.char.map(|(idx, _)| idx)
+    }
+
+    /// Returns the offending character, if parsing failed because of one.
+    ///
+    /// This is `None` for length violations, and for [`ParseErrorKind::InvalidUtf8`] errors,
+    /// which are caused by a byte rather than a character.
+    pub fn offending_char(&self) -> Option<char> {
+        match self.char {
+            Some(_) if self.kind == ParseErrorKind::InvalidUtf8 => None,
+            char => char.map(|(_, char)| char),
+        }
+    }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for ParseAccountError {}
 impl fmt::Display for ParseAccountError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some((actual, max)) = self.len {
+            let (noun, bound, limit) = match self.kind {
+                ParseErrorKind::TooShort => (
+                    if actual == 1 { "byte" } else { "bytes" },
+                    "minimum",
+                    AccountIdRef::MIN_LEN,
+                ),
+                _ => ("bytes", "maximum", max),
+            };
+            return write!(
+                f,
+                "the Account ID is {} {} long, but the {} is {}",
+                actual, noun, bound, limit
+            );
+        }
         let mut buf = self.kind.to_string();
-        if let Some((idx, char)) = self.char {
-            write!(buf, " {:?} at index {}", char, idx)?
+        match self.char {
+            // There is no character to show for bytes that aren't UTF-8.
+            Some((idx, _)) if self.kind == ParseErrorKind::InvalidUtf8 => {
+                write!(buf, " at index {}", idx)?
+            }
+            Some((idx, char)) => write!(buf, " {:?} at index {}", char, idx)?,
+            None => {}
         }
         buf.fmt(f)
     }
@@ -35,10 +140,12 @@ pub enum ParseErrorKind {
     /// The Account ID is too long.
     ///
     /// Returned if the `AccountId` is longer than [`AccountId::MAX_LEN`](crate::AccountId::MAX_LEN).
+    /// The actual length is available from [`ParseAccountError::actual_len`].
     TooLong,
     /// The Account ID is too short.
     ///
     /// Returned if the `AccountId` is shorter than [`AccountId::MIN_LEN`](crate::AccountId::MIN_LEN).
+    /// The actual length is available from [`ParseAccountError::actual_len`].
     TooShort,
     /// The Account ID has a redundant separator.
     ///
@@ -53,6 +160,20 @@ pub enum ParseErrorKind {
     ///
     /// Cases: `ƒelicia.near`, `user@app.com`, `Emily.near`.
     InvalidChar,
+    /// The Account ID is not valid UTF-8.
+    ///
+    /// Only returned when parsing raw bytes, such as with [`AccountId::from_utf8`](crate::AccountId::from_utf8),
+    /// for the first byte that doesn't start a valid UTF-8 sequence. The reported offset is in bytes.
+    ///
+    /// Cases: `b"alice\xff.near"`.
+    InvalidUtf8,
+    /// The length prefix of an encoded Account ID doesn't match the bytes that follow it.
+    ///
+    /// Only returned by [`AccountId::from_bytes`](crate::AccountId::from_bytes), for empty,
+    /// truncated or over-long input.
+    ///
+    /// Cases: `b""`, `b"\x0aalice"`, `b"\x05alice.near"`.
+    InvalidLengthPrefix,
 }
 
 impl fmt::Display for ParseErrorKind {
@@ -62,6 +183,137 @@ impl fmt::Display for ParseErrorKind {
             ParseErrorKind::TooShort => "the Account ID is too short".fmt(f),
             ParseErrorKind::RedundantSeparator => "the Account ID has a redundant separator".fmt(f),
             ParseErrorKind::InvalidChar => "the Account ID contains an invalid character".fmt(f),
+            ParseErrorKind::InvalidUtf8 => "the Account ID is not valid UTF-8".fmt(f),
+            ParseErrorKind::InvalidLengthPrefix => {
+                "the length prefix doesn't match the encoded Account ID".fmt(f)
+            }
         }
     }
 }
+
+/// An error which can be returned when extracting the public key of a NEAR-implicit account.
+///
+/// Returned by [`AccountIdRef::require_implicit_key`](crate::AccountIdRef::require_implicit_key).
+#[non_exhaustive]
+#[derive(Eq, Clone, Debug, PartialEq)]
+pub enum ImplicitError {
+    /// The Account ID is a named account, or an implicit account of a different kind.
+    ///
+    /// Cases: `alice.near`, `system`, `0xb794f5ea0ba39494ce839613fffba74279579268`.
+    NotImplicit,
+    /// The Account ID is made of hex characters only, but isn't 64 characters long.
+    ///
+    /// Holds the actual length of the Account ID.
+    WrongLength(usize),
+    /// The Account ID is 64 characters long, but contains a non-hex character.
+    ///
+    /// Holds the index and value of the first offending character.
+    InvalidHex(usize, char),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ImplicitError {}
+impl fmt::Display for ImplicitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImplicitError::NotImplicit => "the Account ID is not a NEAR-implicit account".fmt(f),
+            ImplicitError::WrongLength(len) => write!(
+                f,
+                "the implicit Account ID is {} characters long, expected 64",
+                len
+            ),
+            ImplicitError::InvalidHex(idx, char) => write!(
+                f,
+                "the implicit Account ID contains a non-hex character {:?} at index {}",
+                char, idx
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use crate::{AccountId, ParseAccountError, ParseErrorKind};
+
+    fn check(err: ParseAccountError, position: Option<usize>, char: Option<char>, message: &str) {
+        assert_eq!(err.position(), position, "{:?}", err);
+        assert_eq!(err.offending_char(), char, "{:?}", err);
+        assert_eq!(err.to_string(), message);
+    }
+
+    #[test]
+    fn test_display() {
+        let parse = |input: &[u8]| AccountId::try_from(input).unwrap_err();
+
+        let err = parse(b"a");
+        assert_eq!(err.kind(), &ParseErrorKind::TooShort);
+        check(
+            err,
+            None,
+            None,
+            "the Account ID is 1 byte long, but the minimum is 2",
+        );
+        let err = parse(&[b'a'; 65]);
+        assert_eq!(err.kind(), &ParseErrorKind::TooLong);
+        check(
+            err,
+            None,
+            None,
+            "the Account ID is 65 bytes long, but the maximum is 64",
+        );
+        let err = parse(b"alice..near");
+        assert_eq!(err.kind(), &ParseErrorKind::RedundantSeparator);
+        check(
+            err,
+            Some(6),
+            Some('.'),
+            "the Account ID has a redundant separator '.' at index 6",
+        );
+        let err = parse(b"Emily.near");
+        assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+        check(
+            err,
+            Some(0),
+            Some('E'),
+            "the Account ID contains an invalid character 'E' at index 0",
+        );
+        check(
+            parse("alice.ƒ".as_bytes()),
+            Some(6),
+            Some('ƒ'),
+            "the Account ID contains an invalid character 'ƒ' at index 6",
+        );
+        let err = parse(b"alice\xff.near");
+        assert_eq!(err.kind(), &ParseErrorKind::InvalidUtf8);
+        check(
+            err,
+            Some(5),
+            None,
+            "the Account ID is not valid UTF-8 at index 5",
+        );
+        let err = AccountId::from_bytes(b"\x0aalice").unwrap_err();
+        assert_eq!(err.kind(), &ParseErrorKind::InvalidLengthPrefix);
+        check(
+            err,
+            None,
+            None,
+            "the length prefix doesn't match the encoded Account ID",
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_error_source() {
+        use std::error::Error;
+
+        let err = "alice..near".parse::<AccountId>().unwrap_err();
+        let err: Box<dyn Error> = Box::new(err);
+        assert!(err.source().is_none());
+        assert_eq!(
+            err.to_string(),
+            "the Account ID has a redundant separator '.' at index 6"
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use alloc::string::String;
+use core::{fmt, hash, ops::Deref};
+
+use crate::validation::MAX_LEN;
+
+/// The storage of an [`AccountId`](crate::AccountId) with the `inline` feature: the bytes and length of
+/// the Account ID, kept inline instead of on the heap.
+///
+/// It behaves like the `Box<str>` it replaces, except that it can't hold more than [`MAX_LEN`] bytes,
+/// so the unchecked constructors panic on longer inputs. All comparisons and hashing go through the string.
+#[derive(Clone)]
+pub(crate) struct InlineStr {
+    len: u8,
+    buf: [u8; MAX_LEN],
+}
+
+impl InlineStr {
+    fn new(s: &str) -> Self {
+        assert!(
+            s.len() <= MAX_LEN,
+            "an inline Account ID can't be longer than {} bytes, got {:?}",
+            MAX_LEN,
+            s
+        );
+        let mut buf = [0; MAX_LEN];
+        buf[..s.len()].copy_from_slice(s.as_bytes());
+        Self {
+            len: s.len() as u8,
+            buf,
+        }
+    }
+}
+
+impl Deref for InlineStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        // Safety: the first `len` bytes are always copied from a `str`, and only ever zeroed since.
+        unsafe { core::str::from_utf8_unchecked(&self.buf[..usize::from(self.len)]) }
+    }
+}
+
+impl AsRef<str> for InlineStr {
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl From<&str> for InlineStr {
+    fn from(s: &str) -> Self {
+        Self::new(s)
+    }
+}
+
+impl From<String> for InlineStr {
+    fn from(s: String) -> Self {
+        Self::new(&s)
+    }
+}
+
+impl From<InlineStr> for String {
+    fn from(s: InlineStr) -> Self {
+        s[..].into()
+    }
+}
+
+impl PartialEq for InlineStr {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for InlineStr {}
+
+impl PartialOrd for InlineStr {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for InlineStr {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+impl hash::Hash for InlineStr {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl fmt::Debug for InlineStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(feature = "abi")]
+impl borsh::BorshSchema for InlineStr {
+    fn add_definitions_recursively(
+        definitions: &mut alloc::collections::BTreeMap<
+            borsh::schema::Declaration,
+            borsh::schema::Definition,
+        >,
+    ) {
+        <alloc::boxed::Box<str>>::add_definitions_recursively(definitions)
+    }
+
+    fn declaration() -> borsh::schema::Declaration {
+        <alloc::boxed::Box<str>>::declaration()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for InlineStr {
+    fn zeroize(&mut self) {
+        self.buf.zeroize();
+        // An empty string rather than NULs that could be read back.
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::alloc::{GlobalAlloc, Layout};
+    use core::cell::Cell;
+    use std::alloc::System;
+
+    use crate::test_data::OK_ACCOUNT_IDS;
+    use crate::{AccountId, AccountIdRef};
+
+    // Counts the allocations of each thread, so that tests running in parallel don't interfere.
+    struct CountingAlloc;
+
+    std::thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAlloc = CountingAlloc;
+
+    fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+        let before = ALLOCATIONS.with(Cell::get);
+        let _ = f();
+        ALLOCATIONS.with(Cell::get) - before
+    }
+
+    #[test]
+    fn test_size() {
+        assert_eq!(core::mem::size_of::<AccountId>(), AccountId::MAX_LEN + 1);
+    }
+
+    #[test]
+    fn test_no_allocation() {
+        assert_eq!(
+            allocations(|| "alice.near".parse::<AccountId>().unwrap()),
+            0
+        );
+        let alice = AccountIdRef::new_or_panic("alice.near");
+        assert_eq!(allocations(|| alice.to_owned()), 0);
+        let alice = alice.to_owned();
+        assert_eq!(allocations(|| alice.clone()), 0);
+        // Converting into heap-allocated types still allocates, of course
+        assert_eq!(allocations(|| String::from(alice.clone())), 1);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        for account_id in OK_ACCOUNT_IDS {
+            let parsed: AccountId = account_id.parse().unwrap();
+            assert_eq!(parsed.as_str(), account_id);
+            assert_eq!(parsed.to_string(), account_id);
+            assert_eq!(
+                format!("{:?}", parsed),
+                format!("AccountId({:?})", account_id)
+            );
+            assert_eq!(String::from(parsed.clone()), account_id);
+            assert_eq!(Box::<str>::from(parsed), account_id.into());
+        }
+
+        let mut accounts: Vec<AccountId> = ["bob.near", "alice.near", "a.near"]
+            .iter()
+            .map(|id| id.parse().unwrap())
+            .collect();
+        accounts.sort();
+        assert_eq!(accounts, ["a.near", "alice.near", "bob.near"]);
+    }
+
+    #[test]
+    #[cfg(feature = "zeroize")]
+    fn test_zeroize() {
+        use zeroize::Zeroize;
+
+        let mut account_id: AccountId = "alice.near".parse().unwrap();
+        account_id.0.zeroize();
+        assert_eq!(account_id.0.len, 0);
+        assert!(account_id.0.buf.iter().all(|&b| b == 0));
+    }
+}
@@ -0,0 +1,307 @@
+use core::iter::FusedIterator;
+
+use crate::AccountIdRef;
+
+/// An iterator over the ancestors of an account.
+///
+/// This `struct` is created by the [`ancestors`] and [`ancestors_with_self`] methods on [`AccountIdRef`].
+/// See their documentation for more.
+///
+/// [`ancestors`]: AccountIdRef::ancestors
+/// [`ancestors_with_self`]: AccountIdRef::ancestors_with_self
+#[derive(Clone, Debug)]
+pub struct Ancestors<'a> {
+    account_id: &'a str,
+    // Offset of the next ancestor to be yielded from the front.
+    front: usize,
+    // Ancestors starting after this offset have already been yielded from the back.
+    back: usize,
+}
+
+impl<'a> Ancestors<'a> {
+    pub(crate) fn new(account_id: &'a AccountIdRef, include_self: bool) -> Self {
+        let account_id = account_id.as_str();
+        // A top-level label that is too short to be an Account ID on its own (`a` in `near.a`)
+        // is not an ancestor. Every other suffix following a `.` is a valid Account ID.
+        let back = match account_id.rfind('.') {
+            Some(idx) if account_id.len() - idx - 1 < crate::validation::MIN_LEN => idx,
+            _ => account_id.len(),
+        };
+        let front = if include_self {
+            0
+        } else {
+            account_id.find('.').map_or(back, |idx| idx + 1)
+        };
+
+        Self {
+            account_id,
+            front: front.min(back),
+            back,
+        }
+    }
+}
+
+impl<'a> Iterator for Ancestors<'a> {
+    type Item = &'a AccountIdRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        let ancestor = &self.account_id[self.front..];
+        self.front = match self.account_id[self.front..self.back].find('.') {
+            Some(idx) => self.front + idx + 1,
+            None => self.back,
+        };
+
+        Some(AccountIdRef::new_unvalidated(ancestor))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = if self.front >= self.back {
+            0
+        } else {
+            let separators = self.account_id.as_bytes()[self.front..self.back]
+                .iter()
+                .filter(|&&b| b == b'.')
+                .count();
+            separators + 1
+        };
+        (len, Some(len))
+    }
+}
+
+impl<'a> DoubleEndedIterator for Ancestors<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        let start = match self.account_id[self.front..self.back].rfind('.') {
+            Some(idx) => self.front + idx + 1,
+            None => self.front,
+        };
+        // Leave out the `.` preceding the yielded ancestor, if any.
+        self.back = start.saturating_sub(1).max(self.front);
+
+        Some(AccountIdRef::new_unvalidated(&self.account_id[start..]))
+    }
+}
+
+impl<'a> ExactSizeIterator for Ancestors<'a> {}
+
+impl<'a> FusedIterator for Ancestors<'a> {}
+
+/// An iterator over the `.`-separated labels of an account, from left to right.
+///
+/// This `struct` is created by the [`labels`] method on [`AccountIdRef`].
+/// See its documentation for more.
+///
+/// [`labels`]: AccountIdRef::labels
+#[derive(Clone, Debug)]
+pub struct Labels<'a> {
+    split: core::str::Split<'a, char>,
+    // Number of labels not yet yielded from either end.
+    remaining: usize,
+}
+
+impl<'a> Labels<'a> {
+    pub(crate) fn new(account_id: &'a AccountIdRef) -> Self {
+        let account_id = account_id.as_str();
+        Self {
+            split: account_id.split('.'),
+            remaining: account_id.bytes().filter(|&b| b == b'.').count() + 1,
+        }
+    }
+}
+
+impl<'a> Iterator for Labels<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let label = self.split.next()?;
+        self.remaining -= 1;
+        Some(label)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a> DoubleEndedIterator for Labels<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let label = self.split.next_back()?;
+        self.remaining -= 1;
+        Some(label)
+    }
+}
+
+impl<'a> ExactSizeIterator for Labels<'a> {}
+
+impl<'a> FusedIterator for Labels<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_data::OK_ACCOUNT_IDS;
+    use crate::AccountIdRef;
+
+    #[test]
+    fn test_ancestors() {
+        let cases: &[(&str, &[&str])] = &[
+            (
+                "app.sub.alice.near",
+                &["sub.alice.near", "alice.near", "near"],
+            ),
+            ("alice.near", &["near"]),
+            ("near", &[]),
+            ("system", &[]),
+            (
+                "0123456789012345678901234567890123456789012345678901234567890123",
+                &[],
+            ),
+            ("near.a", &[]),
+            ("x.ab.c", &["ab.c"]),
+        ];
+
+        for (account_id, expected) in cases {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+
+            let ancestors = account_id.ancestors();
+            assert_eq!(ancestors.len(), expected.len());
+            assert_eq!(
+                ancestors.map(AccountIdRef::as_str).collect::<Vec<_>>(),
+                *expected
+            );
+
+            let mut reversed = expected.to_vec();
+            reversed.reverse();
+            assert_eq!(
+                account_id
+                    .ancestors()
+                    .rev()
+                    .map(AccountIdRef::as_str)
+                    .collect::<Vec<_>>(),
+                reversed
+            );
+        }
+    }
+
+    #[test]
+    fn test_ancestors_with_self() {
+        let cases: &[(&str, &[&str])] = &[
+            ("app.alice.near", &["app.alice.near", "alice.near", "near"]),
+            ("near", &["near"]),
+            (
+                "0123456789012345678901234567890123456789012345678901234567890123",
+                &["0123456789012345678901234567890123456789012345678901234567890123"],
+            ),
+            ("near.a", &["near.a"]),
+        ];
+
+        for (account_id, expected) in cases {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+
+            let ancestors = account_id.ancestors_with_self();
+            assert_eq!(ancestors.len(), expected.len());
+            assert_eq!(
+                ancestors.map(AccountIdRef::as_str).collect::<Vec<_>>(),
+                *expected
+            );
+            assert_eq!(
+                account_id.ancestors_with_self().next_back().unwrap(),
+                expected[expected.len() - 1]
+            );
+        }
+    }
+
+    #[test]
+    fn test_ancestors_mixed_ends() {
+        let account_id = AccountIdRef::new_or_panic("a.b.c.d.near");
+        let mut ancestors = account_id.ancestors();
+
+        assert_eq!(ancestors.len(), 4);
+        assert_eq!(ancestors.next_back().unwrap(), "near");
+        assert_eq!(ancestors.next().unwrap(), "b.c.d.near");
+        assert_eq!(ancestors.len(), 2);
+        assert_eq!(ancestors.next_back().unwrap(), "d.near");
+        assert_eq!(ancestors.next().unwrap(), "c.d.near");
+        assert_eq!(ancestors.len(), 0);
+        assert!(ancestors.next().is_none());
+        assert!(ancestors.next_back().is_none());
+    }
+
+    #[test]
+    fn test_labels() {
+        let cases: &[(&str, &[&str])] = &[
+            ("app.alice.near", &["app", "alice", "near"]),
+            ("near", &["near"]),
+            ("near.a", &["near", "a"]),
+            ("1_4m_n0t-al1c3.near", &["1_4m_n0t-al1c3", "near"]),
+            (
+                "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de",
+                &["98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de"],
+            ),
+            (
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+                &["0xb794f5ea0ba39494ce839613fffba74279579268"],
+            ),
+        ];
+
+        for (account_id, expected) in cases {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            assert_eq!(account_id.labels().len(), expected.len());
+            assert_eq!(account_id.labels().collect::<Vec<_>>(), *expected);
+
+            let mut reversed = expected.to_vec();
+            reversed.reverse();
+            assert_eq!(account_id.labels().rev().collect::<Vec<_>>(), reversed);
+        }
+    }
+
+    #[test]
+    fn test_labels_max_depth() {
+        // 32 labels is the most that fits in `MAX_LEN`.
+        let account_id = format!("{}aa", "a.".repeat(31));
+        let account_id = AccountIdRef::new(&account_id).unwrap();
+        assert_eq!(account_id.len(), AccountIdRef::MAX_LEN);
+
+        let mut labels = account_id.labels();
+        assert_eq!(labels.len(), 32);
+        assert_eq!(labels.next(), Some("a"));
+        assert_eq!(labels.next_back(), Some("aa"));
+        assert_eq!(labels.len(), 30);
+        assert_eq!(labels.size_hint(), (30, Some(30)));
+        assert_eq!(labels.by_ref().count(), 30);
+        assert_eq!(labels.len(), 0);
+        assert_eq!(labels.next(), None);
+    }
+
+    #[test]
+    fn test_labels_are_never_empty() {
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+            assert!(account_id.labels().all(|label| !label.is_empty()));
+            assert_eq!(
+                account_id.labels().collect::<Vec<_>>().join("."),
+                account_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_ancestors_match_parent_chain() {
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id = AccountIdRef::new(account_id).unwrap();
+
+            let mut parent = account_id.parent();
+            for ancestor in account_id.ancestors() {
+                assert!(AccountIdRef::new(ancestor).is_ok());
+                assert_eq!(Some(ancestor), parent);
+                parent = ancestor.parent();
+            }
+            assert_eq!(parent, None);
+        }
+    }
+}
@@ -34,19 +34,90 @@
 //!
 //! assert!("ƒelicia.near".parse::<AccountId>().is_err()); // (ƒ is not f)
 //! ```
+//!
+//! ## `no_std`
+//!
+//! The crate only needs `alloc` when the default `std` feature is disabled.
+//! The `serde`, `borsh`, `sha2`, `zeroize` and `inline` features keep working without `std`, while
+//! `schemars`, `proptest`, `rkyv`, `sqlx`, `diesel` and `wasm` enable it.
+//!
+//! ## Binary serde formats
+//!
+//! With the `serde` feature, Account IDs are serialized as strings in human-readable formats such as JSON,
+//! and as bytes in binary ones. Binary formats that encode both the same way, like bincode and postcard,
+//! read back Account IDs serialized as strings before, and `&AccountIdRef` borrows from their input.
+//! Formats that tell bytes from strings, like CBOR and MessagePack, get a byte string where earlier
+//! releases wrote a text string.
+//!
+//! ## Inline storage
+//!
+//! With the `inline` feature, an [`AccountId`] keeps its bytes in a 64-byte buffer of its own rather than
+//! on the heap. Creating one then never allocates, but converting from or into owned strings copies the
+//! bytes instead of moving the allocation.
+//!
+//! This feature is not additive: since Cargo unifies features, enabling it anywhere changes the
+//! representation of every `AccountId` in the dependency graph, including for crates that didn't ask for it.
+//! It also makes the unchecked constructors behind the `internal_unstable` feature, `new_unvalidated`
+//! and `from_boxed_unchecked`, panic on inputs longer than [`AccountId::MAX_LEN`], which would otherwise
+//! be stored as they are. Libraries should leave it to the final binary to enable it.
+
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+extern crate alloc;
 
+#[macro_use]
+mod macros;
+
+#[cfg(feature = "diesel")]
+mod diesel;
 mod errors;
+#[cfg(all(test, not(feature = "std")))]
+mod no_std_smoke;
 
 mod account_id;
 mod account_id_ref;
+mod account_id_set;
+mod base32;
 #[cfg(feature = "borsh")]
 mod borsh;
+mod builder;
+mod compact;
+#[cfg(feature = "inline")]
+mod inline;
+mod iter;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "schemars")]
+mod schemars;
 #[cfg(feature = "serde")]
 mod serde;
+#[cfg(feature = "sqlx")]
+mod sqlx;
 #[cfg(test)]
 mod test_data;
 mod validation;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "zeroize")]
+mod zeroize;
 
 pub use account_id::AccountId;
 pub use account_id_ref::{AccountIdRef, AccountType};
-pub use errors::{ParseAccountError, ParseErrorKind};
+pub use account_id_set::AccountIdSet;
+pub use builder::AccountIdBuilder;
+pub use compact::CompactAccountId;
+pub use errors::{ImplicitError, ParseAccountError, ParseErrorKind};
+pub use iter::{Ancestors, Labels};
+#[cfg(feature = "rkyv")]
+pub use rkyv::{ArchivedAccountId, CheckAccountIdError};
+#[cfg(all(feature = "serde", feature = "internal_unstable"))]
+pub use serde::serde_unchecked;
+#[cfg(feature = "serde")]
+pub use serde::{borrow_cow, KeyOrAccount, LenBounded, LenientImplicit};
+#[cfg(feature = "std")]
+pub use validation::validate_reader;
+pub use validation::{
+    check_separators, validate, validate_all, validate_const, validate_new_label, validate_segment,
+};
@@ -0,0 +1,102 @@
+/// Creates a `&'static AccountIdRef` that is validated at compile time.
+///
+/// The Account ID has to be a constant expression, usually a string literal.
+/// Invalid Account IDs fail the build instead of panicking at runtime.
+///
+/// ## Examples
+///
+/// ```
+/// use unc_account_id::{account_id, AccountIdRef};
+///
+/// let wrap: &'static AccountIdRef = account_id!("wrap.near");
+/// assert_eq!(wrap, "wrap.near");
+///
+/// const USDT: &AccountIdRef = account_id!("usdt.tether-token.near");
+/// assert!(USDT.is_sub_account_of(account_id!("tether-token.near")));
+/// ```
+///
+/// Invalid Account IDs are rejected by the compiler:
+///
+/// ```compile_fail
+/// let alice = unc_account_id::account_id!("Alice.near");
+/// ```
+///
+/// ```compile_fail
+/// let alice = unc_account_id::account_id!("alice..near");
+/// ```
+#[macro_export]
+macro_rules! account_id {
+    ($account_id:expr) => {{
+        // Binding to a constant forces the validation to run at compile time.
+        const ACCOUNT_ID: &$crate::AccountIdRef = $crate::AccountIdRef::new_or_panic($account_id);
+        ACCOUNT_ID
+    }};
+}
+
+/// Returns whether an Account ID is exactly one of the listed literals.
+///
+/// Works with anything that is [`AsRef<str>`], such as [`AccountId`](crate::AccountId) and
+/// [`&AccountIdRef`](crate::AccountIdRef). Whole Account IDs are compared, so `near` doesn't match
+/// `alice.near`. The literals are validated at compile time, like with [`account_id!`](crate::account_id).
+///
+/// ## Examples
+///
+/// ```
+/// use unc_account_id::{account_matches, AccountId};
+///
+/// let system: AccountId = "system".parse().unwrap();
+/// assert!(account_matches!(system, "near" | "system"));
+///
+/// let alice: AccountId = "alice.near".parse().unwrap();
+/// assert!(!account_matches!(alice, "near" | "system"));
+/// ```
+///
+/// Invalid literals are rejected by the compiler:
+///
+/// ```compile_fail
+/// use unc_account_id::{account_matches, AccountIdRef};
+///
+/// let near = AccountIdRef::new_or_panic("near");
+/// account_matches!(near, "near" | "NEAR");
+/// ```
+#[macro_export]
+macro_rules! account_matches {
+    ($account_id:expr, $($candidate:literal)|+ $(,)?) => {{
+        $(const _: &$crate::AccountIdRef = $crate::AccountIdRef::new_or_panic($candidate);)+
+        matches!(
+            <_ as ::core::convert::AsRef<str>>::as_ref(&$account_id),
+            $($candidate)|+
+        )
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AccountId, AccountIdRef};
+
+    #[test]
+    fn test_account_id() {
+        const WRAP: &AccountIdRef = account_id!("wrap.near");
+
+        assert_eq!(WRAP, AccountIdRef::new("wrap.near").unwrap());
+        assert_eq!(account_id!("near").parent(), None);
+        assert_eq!(
+            account_id!("app.alice.near").parent(),
+            Some(account_id!("alice.near"))
+        );
+    }
+
+    #[test]
+    fn test_account_matches() {
+        let near: AccountId = "near".parse().unwrap();
+        assert!(account_matches!(near, "near"));
+        assert!(account_matches!(near, "system" | "near" | "testnet"));
+        assert!(account_matches!(&near, "near" | "testnet"));
+
+        let alice = AccountIdRef::new_or_panic("alice.near");
+        assert!(account_matches!(alice, "alice.near" | "bob.near"));
+        assert!(!account_matches!(alice, "near" | "alice" | "alice.nea"));
+
+        assert!(!account_matches!(account_id!("system"), "near" | "testnet",));
+    }
+}
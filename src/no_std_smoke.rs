@@ -0,0 +1,30 @@
+//! Smoke tests for builds without the `std` feature.
+//!
+//! Only compiled by `cargo test --no-default-features`, and only uses what `core` and `alloc`
+//! provide, so that a regression in the `no_std` build shows up without a cross-compilation target.
+
+use alloc::{borrow::ToOwned, format, string::ToString};
+
+use crate::{AccountId, AccountIdRef, ParseErrorKind};
+
+#[test]
+fn test_parse_and_display() {
+    let alice: AccountId = "alice.near".parse().unwrap();
+    assert_eq!(alice.as_str(), "alice.near");
+    assert_eq!(alice.to_string(), "alice.near");
+    assert_eq!(format!("{:?}", alice), "AccountId(\"alice.near\")");
+
+    let alice_ref: &AccountIdRef = &alice;
+    assert_eq!(alice_ref.to_owned(), alice);
+    assert_eq!(alice_ref.get_parent_account_id().unwrap(), "near");
+}
+
+#[test]
+fn test_errors() {
+    let err = "alice..near".parse::<AccountId>().unwrap_err();
+    assert_eq!(err.kind(), &ParseErrorKind::RedundantSeparator);
+    assert_eq!(
+        err.to_string(),
+        "the Account ID has a redundant separator '.' at index 6"
+    );
+}
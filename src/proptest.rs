@@ -0,0 +1,241 @@
+//! [`proptest`](https://docs.rs/proptest) strategies generating valid Account IDs.
+//!
+//! Generated Account IDs always respect [`AccountId::MIN_LEN`] and [`AccountId::MAX_LEN`],
+//! and never contain upper-case characters or redundant separators.
+//!
+//! ## Examples
+//!
+//! ```
+//! use proptest::prelude::*;
+//! use unc_account_id::{AccountId, AccountIdRef};
+//!
+//! let near = AccountIdRef::new_or_panic("near");
+//!
+//! proptest!(|(account_id in unc_account_id::proptest::account_id())| {
+//!     prop_assert_eq!(account_id.as_str().parse::<AccountId>().unwrap(), account_id);
+//! });
+//!
+//! proptest!(|(account_id in unc_account_id::proptest::sub_account_of(near))| {
+//!     prop_assert!(account_id.is_descendant_of(near));
+//! });
+//! ```
+
+use core::ops::RangeInclusive;
+
+use proptest::arbitrary::Arbitrary;
+use proptest::collection::vec;
+use proptest::strategy::{BoxedStrategy, Just, Strategy};
+
+use crate::{AccountId, AccountIdRef};
+
+// A label is a run of alphanumeric chunks joined by single `-` or `_` separators.
+const LABEL: &str = r"[a-z0-9]{1,8}([-_][a-z0-9]{1,8}){0,2}";
+
+/// Generates valid Account IDs: mostly named accounts of up to four labels,
+/// with NEAR-implicit and ETH-implicit accounts mixed in.
+///
+/// This is the strategy behind the [`Arbitrary`] implementation of [`AccountId`].
+pub fn account_id() -> impl Strategy<Value = AccountId> {
+    proptest::prop_oneof![
+        8 => named_account_id(1..=4),
+        2 => implicit_account_id(),
+    ]
+}
+
+/// Generates valid top-level named Account IDs, i.e. single labels such as `near`.
+///
+/// Implicit accounts are single labels as well, but are left to [`implicit_account_id`].
+/// The `system` account is never generated, as it isn't a top-level account.
+pub fn top_level_account_id() -> impl Strategy<Value = AccountId> {
+    named_account_id(1..=1).prop_filter("the system account isn't top-level", |id| !id.is_system())
+}
+
+/// Generates NEAR-implicit and ETH-implicit Account IDs, in equal proportions.
+pub fn implicit_account_id() -> impl Strategy<Value = AccountId> {
+    proptest::prop_oneof![
+        "[0-9a-f]{64}".prop_map(|id| parse(&id)),
+        "0x[0-9a-f]{40}".prop_map(|id| parse(&id)),
+    ]
+}
+
+/// Generates valid named Account IDs, made of a number of labels within `num_labels`.
+///
+/// Generated Account IDs that would be too short or too long are skipped.
+///
+/// ## Panics
+///
+/// Panics if `num_labels` allows zero labels.
+pub fn named_account_id(num_labels: RangeInclusive<usize>) -> impl Strategy<Value = AccountId> {
+    assert!(
+        *num_labels.start() > 0,
+        "an Account ID has at least one label"
+    );
+    vec(LABEL, num_labels)
+        .prop_map(|labels| labels.join("."))
+        .prop_filter("Account ID length out of bounds", |id| {
+            (AccountId::MIN_LEN..=AccountId::MAX_LEN).contains(&id.len())
+        })
+        .prop_map(|id| parse(&id))
+}
+
+/// Generates valid descendants of `parent`, one to three labels below it.
+///
+/// Sub-account names that wouldn't fit in [`AccountId::MAX_LEN`] are cut down from the left,
+/// so that deep or long parents still get sub-accounts generated.
+///
+/// ## Panics
+///
+/// Panics if `parent` can't have sub-accounts, e.g. if it is an implicit account or leaves
+/// no room for another label.
+pub fn sub_account_of(parent: &AccountIdRef) -> impl Strategy<Value = AccountId> {
+    assert!(
+        parent.can_be_parent_of_label_len(1),
+        "{} can't have sub-accounts",
+        parent
+    );
+    let room = AccountId::MAX_LEN - parent.len() - 1;
+    (vec(LABEL, 1..=3), Just(parent.to_owned())).prop_map(move |(labels, parent)| {
+        let name = labels.join(".");
+        // A suffix of the name is still valid once stripped of leading separators,
+        // and ends with the same alphanumeric character.
+        let name = name[name.len().saturating_sub(room)..].trim_start_matches(['-', '_', '.']);
+        parse(&alloc::format!("{}.{}", name, parent))
+    })
+}
+
+fn parse(account_id: &str) -> AccountId {
+    account_id.parse().expect("generated an invalid Account ID")
+}
+
+impl Arbitrary for AccountId {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<AccountId>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        account_id().boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use proptest::strategy::ValueTree;
+    use proptest_derive::Arbitrary;
+
+    use super::*;
+
+    #[derive(Debug, Arbitrary)]
+    struct Transfer {
+        sender: AccountId,
+        receiver: AccountId,
+        amount: u128,
+    }
+
+    proptest! {
+        #[test]
+        fn test_arbitrary_account_id_is_valid(account_id: AccountId) {
+            prop_assert!(AccountIdRef::new(account_id.as_str()).is_ok());
+        }
+
+        #[test]
+        fn test_account_id_roundtrip(account_id in account_id()) {
+            prop_assert_eq!(account_id.as_str().parse::<AccountId>().unwrap(), account_id);
+        }
+
+        #[test]
+        fn test_named_account_id(account_id in named_account_id(2..=3)) {
+            prop_assert!((2..=3).contains(&account_id.num_labels()));
+            prop_assert_eq!(account_id.as_str().parse::<AccountId>().unwrap(), account_id);
+        }
+
+        #[test]
+        fn test_top_level_account_id(account_id in top_level_account_id()) {
+            prop_assert!(account_id.is_top_level());
+            prop_assert!(!account_id.is_implicit());
+            prop_assert!(account_id.parent().is_none());
+        }
+
+        #[test]
+        fn test_implicit_account_id(account_id in implicit_account_id()) {
+            prop_assert!(account_id.is_implicit());
+            prop_assert!(account_id.is_top_level());
+            prop_assert_eq!(account_id.to_string().parse::<AccountId>().unwrap(), account_id);
+        }
+
+        #[test]
+        fn test_parent_is_sub_account_of(account_id: AccountId) {
+            match account_id.parent() {
+                Some(parent) => prop_assert!(account_id.is_sub_account_of(parent)),
+                None => prop_assert!(account_id.ancestors().next().is_none()),
+            }
+        }
+
+        #[test]
+        fn test_sub_account_of(
+            (sub_account, parent) in account_id()
+                .prop_filter("no room for sub-accounts", |id| id.can_be_parent_of_label_len(1))
+                .prop_flat_map(|parent| (sub_account_of(&parent), Just(parent)))
+        ) {
+            prop_assert!(sub_account.is_descendant_of(&parent));
+            prop_assert_eq!(sub_account.as_str().parse::<AccountId>().unwrap(), sub_account);
+        }
+
+        #[test]
+        fn test_join(parent: AccountId, child in "[a-z0-9._@-]{0,12}") {
+            if let Ok(account_id) = parent.join(&child) {
+                prop_assert!(account_id.is_sub_account_of(&parent));
+            }
+        }
+
+        #[test]
+        fn test_depth(account_id: AccountId) {
+            let depth = account_id.depth();
+            prop_assert_eq!(depth, account_id.ancestors().count() + 1);
+            // Labels in front of the top-level parent, which is a single label unless its last one is too short
+            let top_level_labels = account_id.top_level_parent().num_labels();
+            prop_assert_eq!(depth, account_id.num_labels() - top_level_labels + 1);
+            prop_assert_eq!(depth == account_id.num_labels(), top_level_labels == 1);
+            prop_assert_eq!(depth == 1, account_id.parent().is_none());
+            if let Some(parent) = account_id.parent() {
+                prop_assert_eq!(depth, parent.depth() + 1);
+            }
+            if account_id.is_top_level() {
+                prop_assert_eq!(depth, 1);
+            }
+        }
+
+        #[test]
+        fn test_arbitrary_derive(transfer: Transfer) {
+            // Account IDs never contain `:`, so they can be used unescaped in a `:`-separated record.
+            let record = format!("{}:{}:{}", transfer.sender, transfer.receiver, transfer.amount);
+            let fields: Vec<_> = record.split(':').collect();
+
+            prop_assert_eq!(fields.len(), 3);
+            prop_assert_eq!(fields[0].parse::<AccountId>().unwrap(), transfer.sender);
+            prop_assert_eq!(fields[1].parse::<AccountId>().unwrap(), transfer.receiver);
+            prop_assert_eq!(fields[2].parse::<u128>().unwrap(), transfer.amount);
+        }
+    }
+
+    #[test]
+    fn test_sub_account_of_long_parent() {
+        let parent = AccountIdRef::new_or_panic(
+            "012345678901234567890123456789012345678901234567890123456.near",
+        );
+        let strategy = sub_account_of(parent);
+        let mut runner = proptest::test_runner::TestRunner::deterministic();
+        for _ in 0..100 {
+            let sub_account = strategy.new_tree(&mut runner).unwrap().current();
+            assert!(sub_account.is_sub_account_of(parent));
+            assert_eq!(sub_account.len(), AccountId::MAX_LEN);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "can't have sub-accounts")]
+    fn test_sub_account_of_implicit() {
+        let _ = sub_account_of(AccountIdRef::new_or_panic(
+            "0123456789012345678901234567890123456789012345678901234567890123",
+        ));
+    }
+}
@@ -0,0 +1,239 @@
+use core::fmt;
+use core::ops::Deref;
+
+use rkyv::bytecheck::CheckBytes;
+use rkyv::ser::Serializer;
+use rkyv::string::{ArchivedString, StringResolver};
+use rkyv::{Archive, Deserialize, Fallible, Serialize};
+
+use crate::{AccountId, AccountIdRef, ParseAccountError};
+
+/// The archived form of an [`AccountId`], readable in place from an `rkyv` archive.
+///
+/// It has the same layout as an archived `String`: a relative pointer to the bytes and their length.
+/// When the archive is accessed through [`rkyv::check_archived_root`], the Account ID is
+/// validated as part of the check, so untrusted archives never yield invalid Account IDs.
+///
+/// ## Examples
+///
+/// ```
+/// use unc_account_id::AccountId;
+///
+/// let accounts: Vec<AccountId> = vec!["alice.near".parse().unwrap(), "bob.near".parse().unwrap()];
+/// let bytes = rkyv::to_bytes::<_, 256>(&accounts).unwrap();
+///
+/// let archived = rkyv::check_archived_root::<Vec<AccountId>>(&bytes).unwrap();
+/// assert_eq!(archived[0].as_str(), "alice.near");
+/// assert!(archived[1].is_sub_account_of(archived[0].parent().unwrap()));
+/// ```
+#[derive(Eq, Ord, Hash, Debug, PartialEq, PartialOrd)]
+#[repr(transparent)]
+pub struct ArchivedAccountId(ArchivedString);
+
+impl ArchivedAccountId {
+    /// Returns the archived Account ID as a string slice.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl Deref for ArchivedAccountId {
+    type Target = AccountIdRef;
+
+    fn deref(&self) -> &Self::Target {
+        AccountIdRef::new_unvalidated(self.as_str())
+    }
+}
+
+impl AsRef<AccountIdRef> for ArchivedAccountId {
+    fn as_ref(&self) -> &AccountIdRef {
+        self
+    }
+}
+
+impl AsRef<str> for ArchivedAccountId {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for ArchivedAccountId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq<AccountId> for ArchivedAccountId {
+    fn eq(&self, other: &AccountId) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<ArchivedAccountId> for AccountId {
+    fn eq(&self, other: &ArchivedAccountId) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Archive for AccountId {
+    type Archived = ArchivedAccountId;
+    type Resolver = StringResolver;
+
+    unsafe fn resolve(&self, pos: usize, resolver: Self::Resolver, out: *mut Self::Archived) {
+        // Safety: `ArchivedAccountId` is a `#[repr(transparent)]` newtype around `ArchivedString`.
+        ArchivedString::resolve_from_str(self.as_str(), pos, resolver, out.cast());
+    }
+}
+
+impl<S: Serializer + ?Sized> Serialize<S> for AccountId {
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(self.as_str(), serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<AccountId, D> for ArchivedAccountId {
+    fn deserialize(&self, _: &mut D) -> Result<AccountId, D::Error> {
+        Ok(AccountId(self.as_str().into()))
+    }
+}
+
+/// An error which can be returned when checking an archived [`AccountId`].
+#[derive(Debug)]
+pub enum CheckAccountIdError<E> {
+    /// The archived string itself is malformed.
+    String(E),
+    /// The archived string is not a valid Account ID.
+    Invalid(ParseAccountError),
+}
+
+impl<E: fmt::Display> fmt::Display for CheckAccountIdError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckAccountIdError::String(err) => write!(f, "invalid archived string: {}", err),
+            CheckAccountIdError::Invalid(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for CheckAccountIdError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CheckAccountIdError::String(err) => Some(err),
+            CheckAccountIdError::Invalid(err) => Some(err),
+        }
+    }
+}
+
+impl<C: ?Sized> CheckBytes<C> for ArchivedAccountId
+where
+    ArchivedString: CheckBytes<C>,
+{
+    type Error = CheckAccountIdError<<ArchivedString as CheckBytes<C>>::Error>;
+
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        let archived = ArchivedString::check_bytes(value.cast(), context)
+            .map_err(CheckAccountIdError::String)?;
+        crate::validation::validate(archived.as_str()).map_err(CheckAccountIdError::Invalid)?;
+        Ok(&*value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use super::*;
+    use crate::test_data::{BAD_ACCOUNT_IDS, OK_ACCOUNT_IDS};
+    use crate::ParseErrorKind;
+
+    #[test]
+    fn test_roundtrip() {
+        let accounts: Vec<AccountId> = OK_ACCOUNT_IDS
+            .iter()
+            .map(|id| id.parse().unwrap())
+            .collect();
+        let bytes = rkyv::to_bytes::<_, 1024>(&accounts).unwrap();
+
+        let archived = rkyv::check_archived_root::<Vec<AccountId>>(&bytes).unwrap();
+        assert_eq!(archived.len(), accounts.len());
+        for (archived, account_id) in archived.iter().zip(&accounts) {
+            assert_eq!(archived.as_str(), account_id.as_str());
+            assert_eq!(archived, account_id);
+            assert_eq!(&**archived, &**account_id);
+            assert_eq!(archived.to_string(), account_id.to_string());
+        }
+
+        let deserialized: Vec<AccountId> = archived.deserialize(&mut rkyv::Infallible).unwrap();
+        assert_eq!(deserialized, accounts);
+    }
+
+    #[test]
+    fn test_hash_and_ord_consistency() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let accounts: Vec<AccountId> = OK_ACCOUNT_IDS
+            .iter()
+            .map(|id| id.parse().unwrap())
+            .collect();
+        let bytes = rkyv::to_bytes::<_, 1024>(&accounts).unwrap();
+        let archived = rkyv::check_archived_root::<Vec<AccountId>>(&bytes).unwrap();
+
+        for (archived, account_id) in archived.iter().zip(&accounts) {
+            assert_eq!(hash(archived), hash(account_id));
+            assert_eq!(hash(&**archived), hash(&**account_id));
+        }
+        for (a, b) in archived.iter().zip(archived.iter().skip(1)) {
+            let (a_live, b_live): (AccountId, AccountId) = (
+                a.deserialize(&mut rkyv::Infallible).unwrap(),
+                b.deserialize(&mut rkyv::Infallible).unwrap(),
+            );
+            assert_eq!(a.cmp(b), a_live.cmp(&b_live));
+        }
+    }
+
+    #[test]
+    fn test_check_bytes() {
+        // An archived `String` has the same layout as an archived `AccountId`.
+        for account_id in BAD_ACCOUNT_IDS {
+            let bytes = rkyv::to_bytes::<_, 256>(&vec![String::from(account_id)]).unwrap();
+            let err = rkyv::check_archived_root::<Vec<AccountId>>(&bytes).unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains(&AccountId::validate(account_id).unwrap_err().to_string()),
+                "{}",
+                err
+            );
+        }
+
+        let bytes = rkyv::to_bytes::<_, 256>(&String::from("alice..near")).unwrap();
+        match rkyv::check_archived_root::<AccountId>(&bytes) {
+            Err(rkyv::validation::CheckArchiveError::CheckBytesError(
+                CheckAccountIdError::Invalid(err),
+            )) => assert_eq!(err.kind(), &ParseErrorKind::RedundantSeparator),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // Malformed archives are still rejected by the string check
+        let mut bytes = rkyv::to_bytes::<_, 256>(&String::from("a-long-enough-account.near"))
+            .unwrap()
+            .to_vec();
+        let len = bytes.len();
+        bytes[len - 4] = 0xff;
+        assert!(matches!(
+            rkyv::check_archived_root::<AccountId>(&bytes),
+            Err(rkyv::validation::CheckArchiveError::CheckBytesError(
+                CheckAccountIdError::String(_)
+            ))
+        ));
+    }
+}
@@ -0,0 +1,153 @@
+use alloc::{borrow::Cow, boxed::Box, string::String, vec};
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Metadata, Schema, SchemaObject, StringValidation};
+use schemars::JsonSchema;
+
+use crate::validation::{MAX_LEN, MIN_LEN};
+use crate::{AccountId, AccountIdRef};
+
+/// The Account ID rules as a regular expression. Together with the length bounds, it accepts exactly
+/// the strings [`validate`](crate::validate) does.
+const PATTERN: &str = r"^(([a-z\d]+[\-_])*[a-z\d]+\.)*([a-z\d]+[\-_])*[a-z\d]+$";
+
+const DESCRIPTION: &str =
+    "NEAR Account ID: lower-case letters and digits, split by `.`, `-` or `_` separators \
+    which can't lead, trail or follow each other. \
+    See https://docs.near.org/docs/concepts/account#account-id-rules";
+
+// `AccountId` and `AccountIdRef` share a name and a schema, so that they end up as a single definition.
+fn schema_name() -> String {
+    "AccountId".into()
+}
+
+fn schema_id() -> Cow<'static, str> {
+    Cow::Borrowed("unc_account_id::AccountId")
+}
+
+fn json_schema() -> Schema {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        metadata: Some(Box::new(Metadata {
+            description: Some(DESCRIPTION.into()),
+            examples: vec![
+                "alice.near".into(),
+                "98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de".into(),
+            ],
+            ..Default::default()
+        })),
+        string: Some(Box::new(StringValidation {
+            min_length: Some(MIN_LEN as u32),
+            max_length: Some(MAX_LEN as u32),
+            pattern: Some(PATTERN.into()),
+        })),
+        ..Default::default()
+    }
+    .into()
+}
+
+impl JsonSchema for AccountId {
+    fn schema_name() -> String {
+        schema_name()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        schema_id()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema()
+    }
+}
+
+impl JsonSchema for AccountIdRef {
+    fn schema_name() -> String {
+        schema_name()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        schema_id()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_data::{BAD_ACCOUNT_IDS, OK_ACCOUNT_IDS};
+
+    fn matches_schema(account_id: &str) -> bool {
+        // `\d` means an ASCII digit in JSON Schema patterns, which follow ECMA 262.
+        let pattern = regex::RegexBuilder::new(PATTERN)
+            .unicode(false)
+            .build()
+            .unwrap();
+        (MIN_LEN..=MAX_LEN).contains(&account_id.len()) && pattern.is_match(account_id)
+    }
+
+    #[test]
+    fn test_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(AccountId)).unwrap();
+        assert_eq!(schema["type"], "string");
+        assert_eq!(schema["minLength"], 2);
+        assert_eq!(schema["maxLength"], 64);
+        assert_eq!(schema["pattern"], PATTERN);
+        for example in schema["examples"].as_array().unwrap() {
+            assert!(AccountIdRef::new(example.as_str().unwrap()).is_ok());
+        }
+
+        assert_eq!(
+            schemars::schema_for!(AccountIdRef),
+            schemars::schema_for!(AccountId)
+        );
+    }
+
+    #[test]
+    fn test_single_definition() {
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        struct Transfer<'a> {
+            sender: AccountId,
+            receiver: &'a AccountIdRef,
+        }
+
+        let schema = serde_json::to_value(schemars::schema_for!(Transfer)).unwrap();
+        let definitions = schema["definitions"].as_object().unwrap();
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(
+            definitions["AccountId"],
+            serde_json::to_value(json_schema()).unwrap()
+        );
+        assert_eq!(
+            schema["properties"]["sender"],
+            schema["properties"]["receiver"]
+        );
+    }
+
+    #[test]
+    fn test_pattern_matches_validation() {
+        for account_id in OK_ACCOUNT_IDS {
+            assert!(matches_schema(account_id), "{:?}", account_id);
+        }
+        for account_id in BAD_ACCOUNT_IDS {
+            assert!(!matches_schema(account_id), "{:?}", account_id);
+        }
+        for account_id in ["١٢.near", "alice.near\n", "a-.b", "a.-b"] {
+            assert!(!matches_schema(account_id), "{:?}", account_id);
+        }
+
+        bolero::check!().for_each(|input: &[u8]| {
+            if let Ok(account_id) = core::str::from_utf8(input) {
+                assert_eq!(
+                    matches_schema(account_id),
+                    crate::validate(account_id).is_ok(),
+                    "{:?}",
+                    account_id
+                );
+            }
+        });
+    }
+}
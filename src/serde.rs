@@ -1,4 +1,7 @@
-use crate::AccountIdRef;
+use alloc::{format, string::String};
+use core::{fmt, marker::PhantomData, ops::Deref};
+
+use crate::{AccountIdRef, ParseAccountError};
 
 use super::AccountId;
 
@@ -9,16 +12,24 @@ impl ser::Serialize for AccountId {
     where
         S: ser::Serializer,
     {
-        self.0.serialize(serializer)
+        (**self).serialize(serializer)
     }
 }
 
+// Human-readable formats get a string, and binary formats the raw bytes. Most binary formats,
+// such as bincode and postcard, encode both the same way, so the data stays compatible.
+// Formats with distinct string and byte types, such as CBOR and MessagePack, now write a byte
+// string where earlier releases wrote a text string. Self-describing ones still read back either.
 impl ser::Serialize for AccountIdRef {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: ser::Serializer,
     {
-        self.0.serialize(serializer)
+        if serializer.is_human_readable() {
+            serializer.serialize_str(self.as_str())
+        } else {
+            serializer.serialize_bytes(self.as_bytes())
+        }
     }
 }
 
@@ -27,28 +38,547 @@ impl<'de> de::Deserialize<'de> for AccountId {
     where
         D: de::Deserializer<'de>,
     {
-        let account_id = Box::<str>::deserialize(deserializer)?;
-        crate::validation::validate(&account_id).map_err(|err| {
-            de::Error::custom(format!("invalid value: \"{}\", {}", account_id, err))
-        })?;
-        Ok(AccountId(account_id))
+        let account_id = deserialize_string(deserializer)?;
+        crate::validation::validate(&account_id).map_err(|err| invalid_value(&account_id, err))?;
+        Ok(AccountId(account_id.into()))
+    }
+}
+
+impl<'de: 'a, 'a> de::Deserialize<'de> for &'a AccountIdRef {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            <&'a str as de::Deserialize>::deserialize(deserializer)
+                .and_then(|s| Self::try_from(s).map_err(|err| invalid_value(s, err)))
+        } else {
+            deserializer.deserialize_bytes(BorrowedVisitor(PhantomData))
+        }
+    }
+}
+
+struct BorrowedVisitor<'a>(PhantomData<&'a AccountIdRef>);
+
+impl<'de: 'a, 'a> de::Visitor<'de> for BorrowedVisitor<'a> {
+    type Value = &'a AccountIdRef;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a borrowed NEAR Account ID")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        AccountIdRef::new(v).map_err(|err| invalid_value(v, err))
+    }
+
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        AccountIdRef::from_utf8(v).map_err(|err| invalid_value(&String::from_utf8_lossy(v), err))
+    }
+}
+
+/// Deserializes the string of an Account ID, without validating it.
+///
+/// Binary formats are asked for bytes, but may hand back a string as well.
+fn deserialize_string<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        <String as de::Deserialize>::deserialize(deserializer)
+    } else {
+        deserializer.deserialize_byte_buf(StringVisitor)
+    }
+}
+
+struct StringVisitor;
+
+impl<'de> de::Visitor<'de> for StringVisitor {
+    type Value = String;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a NEAR Account ID")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(v.into())
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        match core::str::from_utf8(v) {
+            Ok(account_id) => Ok(account_id.into()),
+            Err(_) => Err(invalid_bytes(v)),
+        }
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: alloc::vec::Vec<u8>) -> Result<Self::Value, E> {
+        String::from_utf8(v).map_err(|err| invalid_bytes(err.as_bytes()))
+    }
+}
+
+/// The error for bytes that aren't UTF-8, and so can't be an Account ID.
+fn invalid_bytes<E: de::Error>(v: &[u8]) -> E {
+    let err = crate::validation::validate_bytes(v).expect_err("Account IDs are valid UTF-8");
+    invalid_value(&String::from_utf8_lossy(v), err)
+}
+
+/// How many characters of a rejected value end up in an error message.
+const MAX_SHOWN_CHARS: usize = 80;
+
+/// The error for a value that isn't a valid Account ID: the value itself, cut down to
+/// [`MAX_SHOWN_CHARS`], followed by why it was rejected and where.
+fn invalid_value<E: de::Error>(v: &str, err: ParseAccountError) -> E {
+    match v.char_indices().nth(MAX_SHOWN_CHARS) {
+        Some((end, _)) => E::custom(format!("invalid value: \"{}\"..., {}", &v[..end], err)),
+        None => E::custom(format!("invalid value: \"{}\", {}", v, err)),
+    }
+}
+
+/// An Account ID with a tighter maximum length of `N` characters.
+///
+/// On deserialization, the regular Account ID validation of `T` runs first, and then
+/// the length is checked against `N`, resulting in a [`ParseErrorKind::TooLong`](crate::ParseErrorKind::TooLong) error past the bound.
+///
+/// This lets a protocol express its own limits in the type:
+///
+/// ```
+/// use unc_account_id::{AccountId, LenBounded};
+///
+/// #[derive(serde::Deserialize)]
+/// struct Registration {
+///     account_id: LenBounded<AccountId, 32>,
+/// }
+///
+/// let ok: Registration = serde_json::from_str(r#"{"account_id": "alice.near"}"#).unwrap();
+/// assert_eq!(*ok.account_id, "alice.near");
+///
+/// let too_long = r#"{"account_id": "a-very-long-account-name-for-this.near"}"#;
+/// assert!(serde_json::from_str::<Registration>(too_long).is_err());
+/// ```
+#[derive(Eq, Ord, Hash, Clone, Debug, PartialEq, PartialOrd)]
+pub struct LenBounded<T, const N: usize>(T);
+
+impl<T: AsRef<str>, const N: usize> LenBounded<T, N> {
+    /// Wraps an Account ID, checking that it is at most `N` characters long.
+    pub fn new(account_id: T) -> Result<Self, ParseAccountError> {
+        Self::check_len(account_id.as_ref())?;
+        Ok(Self(account_id))
+    }
+
+    fn check_len(account_id: &str) -> Result<(), ParseAccountError> {
+        if account_id.len() > N {
+            return Err(ParseAccountError::too_long(account_id.len(), N));
+        }
+        Ok(())
+    }
+}
+
+impl<T, const N: usize> LenBounded<T, N> {
+    /// Unwraps the inner Account ID.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, const N: usize> Deref for LenBounded<T, N> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: ser::Serialize, const N: usize> ser::Serialize for LenBounded<T, N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T, const N: usize> de::Deserialize<'de> for LenBounded<T, N>
+where
+    T: de::Deserialize<'de> + AsRef<str>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let account_id = T::deserialize(deserializer)?;
+        Self::check_len(account_id.as_ref())
+            .map_err(|err| invalid_value(account_id.as_ref(), err))?;
+        Ok(Self(account_id))
+    }
+}
+
+/// An Account ID that accepts implicit accounts in any letter case.
+///
+/// Some wallets send the hex of implicit accounts in upper or mixed case. On deserialization,
+/// input shaped like an implicit account (64 hex characters, or `0x` followed by 40 hex characters,
+/// ignoring case) is lowercased before validation. Anything else, including named accounts, is
+/// validated as strictly as [`AccountId`] is, so `Alice.near` is still rejected.
+///
+/// It is always serialized as the normalized Account ID.
+///
+/// ## Examples
+///
+/// ```
+/// use unc_account_id::{AccountId, LenientImplicit};
+///
+/// let eth: LenientImplicit<AccountId> =
+///     serde_json::from_str(r#""0xB794F5EA0BA39494CE839613FFFBA74279579268""#).unwrap();
+/// assert_eq!(*eth, "0xb794f5ea0ba39494ce839613fffba74279579268");
+///
+/// assert!(serde_json::from_str::<LenientImplicit<AccountId>>(r#""Alice.near""#).is_err());
+/// ```
+#[derive(Eq, Ord, Hash, Clone, Debug, PartialEq, PartialOrd)]
+pub struct LenientImplicit<T>(T);
+
+impl<T> LenientImplicit<T> {
+    /// Unwraps the inner Account ID.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for LenientImplicit<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: ser::Serialize> ser::Serialize for LenientImplicit<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T: From<AccountId>> de::Deserialize<'de> for LenientImplicit<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let mut account_id = deserialize_string(deserializer)?;
+        if has_implicit_shape(&account_id) {
+            account_id.make_ascii_lowercase();
+        }
+        crate::validation::validate(&account_id).map_err(|err| invalid_value(&account_id, err))?;
+        Ok(Self(AccountId(account_id.into()).into()))
+    }
+}
+
+/// Returns `true` if the Account ID would be implicit once lowercased.
+fn has_implicit_shape(account_id: &str) -> bool {
+    let hex = match account_id.len() {
+        64 => account_id,
+        42 if account_id.as_bytes()[..2].eq_ignore_ascii_case(b"0x") => &account_id[2..],
+        _ => return false,
+    };
+    hex.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// An Account ID that can be deserialized either from a string or from a 32-byte public key.
+///
+/// This is meant for fields where legacy encodings send the public key of an implicit account
+/// instead of the Account ID. The input is interpreted as follows:
+///
+/// 1. A string is parsed as an Account ID.
+/// 2. Exactly 32 bytes, or a sequence of 32 integers, are taken as an ED25519 public key and
+///    turned into the matching NEAR-implicit account (its lowercase hex encoding).
+///    This takes precedence even if the bytes happen to be valid UTF-8.
+/// 3. Bytes of any other length are parsed as a UTF-8 Account ID.
+///
+/// Deciding between these requires a self-describing format such as JSON.
+/// It is always serialized as a string.
+///
+/// ## Examples
+///
+/// ```
+/// use unc_account_id::KeyOrAccount;
+///
+/// let alice: KeyOrAccount = serde_json::from_str(r#""alice.near""#).unwrap();
+/// assert_eq!(*alice, "alice.near");
+///
+/// let key: KeyOrAccount = serde_json::from_str(&format!("{:?}", [0xab_u8; 32])).unwrap();
+/// assert_eq!(*key, "ab".repeat(32));
+/// assert!(key.is_near_implicit());
+/// ```
+#[derive(Eq, Ord, Hash, Clone, Debug, PartialEq, PartialOrd)]
+pub struct KeyOrAccount(AccountId);
+
+impl KeyOrAccount {
+    /// Unwraps the inner Account ID.
+    pub fn into_inner(self) -> AccountId {
+        self.0
+    }
+
+    fn from_key(key: &[u8]) -> Self {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+        let account_id: String = key
+            .iter()
+            .flat_map(|b| [HEX[usize::from(b >> 4)], HEX[usize::from(b & 0xf)]])
+            .map(char::from)
+            .collect();
+        Self(AccountId(account_id.into()))
+    }
+}
+
+impl Deref for KeyOrAccount {
+    type Target = AccountId;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<KeyOrAccount> for AccountId {
+    fn from(value: KeyOrAccount) -> Self {
+        value.0
+    }
+}
+
+impl ser::Serialize for KeyOrAccount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        // Not as bytes, which would read back as a public key if there are 32 of them.
+        self.0.as_str().serialize(serializer)
     }
 }
 
-impl<'de> de::Deserialize<'de> for &'de AccountIdRef {
+impl<'de> de::Deserialize<'de> for KeyOrAccount {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: de::Deserializer<'de>,
     {
-        <&str as de::Deserialize>::deserialize(deserializer)
-            .and_then(|s| Self::try_from(s).map_err(de::Error::custom))
+        deserializer.deserialize_any(KeyOrAccountVisitor)
+    }
+}
+
+const PUBLIC_KEY_LEN: usize = 32;
+
+struct KeyOrAccountVisitor;
+
+impl<'de> de::Visitor<'de> for KeyOrAccountVisitor {
+    type Value = KeyOrAccount;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a NEAR Account ID or a 32-byte public key")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        match crate::validation::validate(v) {
+            Ok(()) => Ok(KeyOrAccount(AccountId(v.into()))),
+            Err(err) => Err(invalid_value(v, err)),
+        }
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        if v.len() == PUBLIC_KEY_LEN {
+            return Ok(KeyOrAccount::from_key(v));
+        }
+        match core::str::from_utf8(v) {
+            Ok(account_id) => self.visit_str(account_id),
+            Err(_) => Err(E::invalid_value(de::Unexpected::Bytes(v), &self)),
+        }
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut key = [0; PUBLIC_KEY_LEN];
+        for (idx, byte) in key.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(idx, &self))?;
+        }
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(PUBLIC_KEY_LEN + 1, &self));
+        }
+        Ok(KeyOrAccount::from_key(&key))
+    }
+}
+
+/// (De)serialization of `Cow<'a, AccountIdRef>` that borrows from the input whenever possible.
+///
+/// serde's own implementation for [`Cow`](alloc::borrow::Cow) always deserializes into the owned variant.
+/// Use this module with `#[serde(borrow, with = "unc_account_id::borrow_cow")]` to get the
+/// borrowed variant when the data allows it, and an owned [`AccountId`] otherwise (e.g. for JSON strings
+/// containing escape sequences). Unlike with `&AccountIdRef`, both cases succeed.
+///
+/// ## Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use unc_account_id::AccountIdRef;
+///
+/// #[derive(serde::Deserialize)]
+/// struct Transfer<'a> {
+///     #[serde(borrow, with = "unc_account_id::borrow_cow")]
+///     receiver_id: Cow<'a, AccountIdRef>,
+/// }
+///
+/// let transfer: Transfer = serde_json::from_str(r#"{"receiver_id": "alice.near"}"#).unwrap();
+/// assert!(matches!(transfer.receiver_id, Cow::Borrowed(_)));
+///
+/// let transfer: Transfer = serde_json::from_str(r#"{"receiver_id": "\u0061lice.near"}"#).unwrap();
+/// assert!(matches!(transfer.receiver_id, Cow::Owned(_)));
+/// assert_eq!(transfer.receiver_id.as_ref(), "alice.near");
+/// ```
+pub mod borrow_cow {
+    use alloc::{borrow::Cow, string::String};
+    use core::{fmt, marker::PhantomData};
+
+    use serde::{de, ser};
+
+    use crate::{AccountId, AccountIdRef};
+
+    /// Serializes the borrowed or owned Account ID like [`AccountIdRef`] does.
+    // `with` modules have to take a reference to the field type.
+    #[allow(clippy::ptr_arg)]
+    pub fn serialize<S>(
+        account_id: &Cow<'_, AccountIdRef>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        ser::Serialize::serialize(account_id.as_ref(), serializer)
+    }
+
+    /// Deserializes an Account ID, borrowing it from the input if the deserializer supports it.
+    pub fn deserialize<'de: 'a, 'a, D>(deserializer: D) -> Result<Cow<'a, AccountIdRef>, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(CowVisitor(PhantomData))
+        } else {
+            deserializer.deserialize_bytes(CowVisitor(PhantomData))
+        }
+    }
+
+    struct CowVisitor<'a>(PhantomData<&'a AccountIdRef>);
+
+    impl<'de: 'a, 'a> de::Visitor<'de> for CowVisitor<'a> {
+        type Value = Cow<'a, AccountIdRef>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a NEAR Account ID")
+        }
+
+        fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+            AccountIdRef::new(v)
+                .map(Cow::Borrowed)
+                .map_err(|err| super::invalid_value(v, err))
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            self.visit_string(v.into())
+        }
+
+        fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+            match crate::validation::validate(&v) {
+                Ok(()) => Ok(Cow::Owned(AccountId(v.into()))),
+                Err(err) => Err(super::invalid_value(&v, err)),
+            }
+        }
+
+        fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+            match core::str::from_utf8(v) {
+                Ok(account_id) => self.visit_borrowed_str(account_id),
+                Err(_) => Err(super::invalid_bytes(v)),
+            }
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            match core::str::from_utf8(v) {
+                Ok(account_id) => self.visit_str(account_id),
+                Err(_) => Err(super::invalid_bytes(v)),
+            }
+        }
+    }
+}
+
+/// Deserializes an [`AccountId`] without validating it, for replaying data that predates the current rules.
+///
+/// Use this module with `#[serde(with = "unc_account_id::serde_unchecked")]` on an `AccountId` field.
+/// Serialization is the same as for `AccountId`, and the default `Deserialize` implementation keeps validating.
+///
+/// Like [`AccountId::new_unvalidated`], this is only available with the `internal_unstable` feature.
+/// The resulting Account IDs may be invalid, and every method relying on the Account ID rules gives
+/// meaningless results for them, so they should be checked with [`AccountId::validate`] before use.
+/// With the `validate-unchecked` feature enabled, invalid Account IDs are rejected anyway, with the same
+/// error as the default `Deserialize` implementation. With the `inline` feature enabled, so are the ones
+/// longer than [`AccountId::MAX_LEN`].
+///
+/// ## Examples
+///
+/// ```
+/// use unc_account_id::AccountId;
+///
+/// #[derive(serde::Deserialize)]
+/// struct Receipt {
+///     #[serde(with = "unc_account_id::serde_unchecked")]
+///     receiver_id: AccountId,
+/// }
+///
+/// let receipt: Receipt = serde_json::from_str(r#"{"receiver_id": "alice.near"}"#).unwrap();
+/// assert_eq!(receipt.receiver_id, "alice.near");
+/// # #[cfg(not(feature = "validate-unchecked"))]
+/// # {
+///
+/// let receipt: Receipt = serde_json::from_str(r#"{"receiver_id": "Alice.near"}"#).unwrap();
+/// assert!(AccountId::validate(receipt.receiver_id.as_str()).is_err());
+/// # }
+/// ```
+#[cfg(feature = "internal_unstable")]
+pub mod serde_unchecked {
+    use serde::{de, ser};
+
+    use crate::AccountId;
+
+    /// Serializes the Account ID like its `Serialize` implementation does.
+    pub fn serialize<S>(account_id: &AccountId, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        ser::Serialize::serialize(account_id, serializer)
+    }
+
+    /// Deserializes an Account ID, skipping validation unless the `validate-unchecked` feature is enabled.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<AccountId, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let account_id = super::deserialize_string(deserializer)?;
+        // Inline Account IDs have no room for more.
+        #[cfg(feature = "inline")]
+        if account_id.len() > crate::validation::MAX_LEN {
+            let err =
+                crate::ParseAccountError::too_long(account_id.len(), crate::validation::MAX_LEN);
+            return Err(super::invalid_value(&account_id, err));
+        }
+        #[cfg(feature = "validate-unchecked")]
+        crate::validation::validate(&account_id)
+            .map_err(|err| super::invalid_value(&account_id, err))?;
+        Ok(AccountId(account_id.into()))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::{KeyOrAccount, LenBounded, LenientImplicit};
     use crate::test_data::{BAD_ACCOUNT_IDS, OK_ACCOUNT_IDS};
-    use crate::AccountId;
+    use crate::{AccountId, AccountIdRef, ParseErrorKind};
 
     use serde_json::json;
 
@@ -81,6 +611,491 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_borrowed_account_id_ref() {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Transfer<'a> {
+            #[serde(borrow)]
+            receiver_id: &'a AccountIdRef,
+        }
+
+        let input = r#"{"receiver_id":"alice.near"}"#;
+        let transfer: Transfer = serde_json::from_str(input).unwrap();
+        assert_eq!(transfer.receiver_id, "alice.near");
+
+        // Zero-copy: the Account ID points into the input
+        let range = input.as_bytes().as_ptr_range();
+        assert!(range.contains(&transfer.receiver_id.as_bytes().as_ptr()));
+
+        assert_eq!(serde_json::to_string(&transfer).unwrap(), input);
+
+        for account_id in OK_ACCOUNT_IDS {
+            let json = json!(account_id).to_string();
+            let account_id_ref: &AccountIdRef = serde_json::from_str(&json).unwrap();
+            assert_eq!(account_id_ref, account_id);
+        }
+
+        for account_id in BAD_ACCOUNT_IDS {
+            let json = json!(account_id).to_string();
+            if let Err(err) = serde_json::from_str::<&AccountIdRef>(&json) {
+                // Some of the invalid IDs have to be escaped, and fail before validation
+                if !json[1..json.len() - 1].contains('\\') {
+                    let expected = format!(
+                        "invalid value: \"{}\", {}",
+                        account_id,
+                        AccountIdRef::new(account_id).unwrap_err()
+                    );
+                    assert!(err.to_string().starts_with(&expected), "{}", err);
+                }
+            } else {
+                panic!(
+                    "successfully deserialized invalid account ID {:?}",
+                    account_id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_borrowed_account_id_ref_from_escaped_input() {
+        // A valid Account ID, but spelled with an escape sequence: it has to be unescaped into
+        // a new buffer, so it can't be borrowed from the input.
+        let err = serde_json::from_str::<&AccountIdRef>(r#""alice\u002enear""#).unwrap_err();
+        assert!(
+            err.to_string().contains("expected a borrowed string"),
+            "{}",
+            err
+        );
+
+        // Owned Account IDs don't have this limitation
+        let account_id: AccountId = serde_json::from_str(r#""alice\u002enear""#).unwrap();
+        assert_eq!(account_id, "alice.near");
+    }
+
+    #[test]
+    fn test_error_messages() {
+        #[derive(Debug, serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Config<'a> {
+            owner_id: AccountId,
+            #[serde(borrow)]
+            operator_id: &'a AccountIdRef,
+        }
+
+        let check = |owner_id: &str, operator_id: &str, expected: &str| {
+            let json = format!(
+                r#"{{"owner_id":"{}","operator_id":"{}"}}"#,
+                owner_id, operator_id
+            );
+            let err = serde_json::from_str::<Config>(&json).unwrap_err();
+            assert_eq!(err.to_string(), expected);
+        };
+
+        check(
+            "ErinMoriarty.near",
+            "alice.near",
+            "invalid value: \"ErinMoriarty.near\", the Account ID contains an invalid character 'E' at index 0 at line 1 column 31",
+        );
+        check(
+            "alice.near",
+            "bob..near",
+            "invalid value: \"bob..near\", the Account ID has a redundant separator '.' at index 4 at line 1 column 51",
+        );
+        check(
+            "a",
+            "alice.near",
+            "invalid value: \"a\", the Account ID is 1 byte long, but the minimum is 2 at line 1 column 15",
+        );
+
+        // Long values are cut down to their first 80 characters, not bytes
+        let long = "a".repeat(100);
+        check(
+            &long,
+            "alice.near",
+            &format!(
+                "invalid value: \"{}\"..., the Account ID is 100 bytes long, but the maximum is 64 at line 1 column 114",
+                &long[..80]
+            ),
+        );
+        let long = "ä".repeat(100);
+        check(
+            "alice.near",
+            &long,
+            &format!(
+                "invalid value: \"{}\"..., the Account ID is 200 bytes long, but the maximum is 64 at line 1 column 242",
+                "ä".repeat(80)
+            ),
+        );
+    }
+
+    #[test]
+    fn test_borrow_cow() {
+        use std::borrow::Cow;
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Transfer<'a> {
+            #[serde(borrow, with = "crate::borrow_cow")]
+            receiver_id: Cow<'a, AccountIdRef>,
+        }
+
+        let input = r#"{"receiver_id":"alice.near"}"#;
+        let transfer: Transfer = serde_json::from_str(input).unwrap();
+        assert!(matches!(transfer.receiver_id, Cow::Borrowed(_)));
+        assert_eq!(serde_json::to_string(&transfer).unwrap(), input);
+
+        let escaped = r#"{"receiver_id":"\u0061lice.near"}"#;
+        let owned: Transfer = serde_json::from_str(escaped).unwrap();
+        assert!(matches!(owned.receiver_id, Cow::Owned(_)));
+        assert_eq!(owned, transfer);
+        assert_eq!(serde_json::to_string(&owned).unwrap(), input);
+
+        // Owned values still hold valid Account IDs
+        let err =
+            serde_json::from_str::<Transfer>(r#"{"receiver_id":"\u0041lice.near"}"#).unwrap_err();
+        assert!(err.to_string().starts_with(
+            "invalid value: \"Alice.near\", the Account ID contains an invalid character"
+        ));
+
+        let err = serde_json::from_str::<Transfer>(r#"{"receiver_id":"alice..near"}"#).unwrap_err();
+        assert!(err.to_string().starts_with(
+            "invalid value: \"alice..near\", the Account ID has a redundant separator"
+        ));
+
+        // Binary formats borrow directly from the input
+        for account_id in OK_ACCOUNT_IDS {
+            let transfer = Transfer {
+                receiver_id: Cow::Owned(account_id.parse().unwrap()),
+            };
+            let bytes = bincode::serialize(&transfer).unwrap();
+            let deserialized: Transfer = bincode::deserialize(&bytes).unwrap();
+            assert!(matches!(deserialized.receiver_id, Cow::Borrowed(_)));
+            assert_eq!(deserialized, transfer);
+        }
+
+        for account_id in BAD_ACCOUNT_IDS {
+            let bytes = bincode::serialize(account_id).unwrap();
+            assert!(
+                bincode::deserialize::<Transfer>(&bytes).is_err(),
+                "{:?}",
+                account_id
+            );
+        }
+    }
+
+    #[test]
+    fn test_binary_formats() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Transfer<'a> {
+            sender_id: AccountId,
+            #[serde(borrow)]
+            receiver_id: &'a AccountIdRef,
+        }
+
+        for account_id in OK_ACCOUNT_IDS {
+            let owned: AccountId = account_id.parse().unwrap();
+            let bytes = bincode::serialize(&owned).unwrap();
+            // Encoded like the string, so data serialized before still reads back
+            assert_eq!(bytes, bincode::serialize(account_id).unwrap());
+            assert_eq!(bincode::serialize(&*owned).unwrap(), bytes);
+
+            assert_eq!(bincode::deserialize::<AccountId>(&bytes).unwrap(), owned);
+            assert_eq!(
+                bincode::deserialize_from::<_, AccountId>(bytes.as_slice()).unwrap(),
+                owned
+            );
+
+            // Zero-copy: the Account ID points into the input
+            let borrowed: &AccountIdRef = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(borrowed, account_id);
+            let range = bytes.as_ptr_range();
+            assert!(range.contains(&borrowed.as_bytes().as_ptr()));
+
+            // Human-readable formats are unaffected
+            assert_eq!(serde_json::to_value(&owned).unwrap(), json!(account_id));
+        }
+
+        let transfer = Transfer {
+            sender_id: "alice.near".parse().unwrap(),
+            receiver_id: AccountIdRef::new_or_panic("bob.near"),
+        };
+        let bytes = bincode::serialize(&transfer).unwrap();
+        assert_eq!(bincode::deserialize::<Transfer>(&bytes).unwrap(), transfer);
+
+        for account_id in BAD_ACCOUNT_IDS {
+            let bytes = bincode::serialize(account_id).unwrap();
+            let err = bincode::deserialize::<AccountId>(&bytes).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                serde_json::from_value::<AccountId>(json!(account_id))
+                    .unwrap_err()
+                    .to_string()
+            );
+            assert!(bincode::deserialize_from::<_, AccountId>(bytes.as_slice()).is_err());
+            assert!(bincode::deserialize::<&AccountIdRef>(&bytes).is_err());
+        }
+
+        // Bytes that aren't UTF-8
+        let bytes = bincode::serialize(&b"alice\xff.near"[..]).unwrap();
+        let expected =
+            "invalid value: \"alice\u{fffd}.near\", the Account ID is not valid UTF-8 at index 5";
+        let err = bincode::deserialize::<AccountId>(&bytes).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+        let err = bincode::deserialize_from::<_, AccountId>(bytes.as_slice()).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+        let err = bincode::deserialize::<&AccountIdRef>(&bytes).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+
+    #[test]
+    fn test_postcard() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Transfer<'a> {
+            sender_id: AccountId,
+            #[serde(borrow)]
+            receiver_id: &'a AccountIdRef,
+        }
+
+        for account_id in OK_ACCOUNT_IDS {
+            let owned: AccountId = account_id.parse().unwrap();
+            let bytes = postcard::to_allocvec(&owned).unwrap();
+            // Bytes and strings share an encoding in postcard as well
+            assert_eq!(bytes, postcard::to_allocvec(account_id).unwrap());
+            assert_eq!(postcard::from_bytes::<AccountId>(&bytes).unwrap(), owned);
+
+            let borrowed: &AccountIdRef = postcard::from_bytes(&bytes).unwrap();
+            assert_eq!(borrowed, account_id);
+            assert!(bytes.as_ptr_range().contains(&borrowed.as_bytes().as_ptr()));
+        }
+
+        let transfer = Transfer {
+            sender_id: "alice.near".parse().unwrap(),
+            receiver_id: AccountIdRef::new_or_panic("bob.near"),
+        };
+        let bytes = postcard::to_allocvec(&transfer).unwrap();
+        assert_eq!(postcard::from_bytes::<Transfer>(&bytes).unwrap(), transfer);
+
+        for account_id in BAD_ACCOUNT_IDS {
+            let bytes = postcard::to_allocvec(account_id).unwrap();
+            assert!(postcard::from_bytes::<AccountId>(&bytes).is_err());
+            assert!(postcard::from_bytes::<&AccountIdRef>(&bytes).is_err());
+        }
+    }
+
+    #[test]
+    fn test_lenient_implicit() {
+        let from_json = |account_id: &str| {
+            serde_json::from_value::<LenientImplicit<AccountId>>(json!(account_id))
+                .map(LenientImplicit::into_inner)
+        };
+
+        // Implicit accounts are normalized
+        for (account_id, expected) in [
+            (
+                "0xB794F5EA0BA39494CE839613FFFBA74279579268",
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+            ),
+            (
+                "0Xb794f5ea0ba39494ce839613fffba74279579268",
+                "0xb794f5ea0ba39494ce839613fffba74279579268",
+            ),
+            (
+                "248E104D1D4764D713C4211C13808C8FC887869C580F4178E60538AC5C2A0B26",
+                "248e104d1d4764d713c4211c13808c8fc887869c580f4178e60538ac5c2a0b26",
+            ),
+            (
+                "248e104d1d4764d713c4211c13808c8fc887869C580F4178E60538AC5C2A0B26",
+                "248e104d1d4764d713c4211c13808c8fc887869c580f4178e60538ac5c2a0b26",
+            ),
+        ] {
+            let account_id = from_json(account_id).unwrap();
+            assert_eq!(account_id, expected);
+            assert!(account_id.is_implicit());
+            assert_eq!(
+                serde_json::to_value(LenientImplicit(account_id)).unwrap(),
+                json!(expected)
+            );
+        }
+
+        // Named accounts stay strict
+        for account_id in [
+            "Alice.near",
+            "ALICE.NEAR",
+            "0xB794F5EA0BA39494CE839613FFFBA74279579268.near",
+            // Implicit lengths, but not hex
+            "0xG794F5EA0BA39494CE839613FFFBA74279579268",
+            "248E104D1D4764D713C4211C13808C8FC887869C580F4178E60538AC5C2A0B2G",
+            // Hex, but not an implicit length
+            "0xB794F5EA0BA39494CE839613FFFBA7427957926",
+            "ABCDEF",
+            // Not ASCII
+            "€794F5EA0BA39494CE839613FFFBA74279579268",
+        ] {
+            let err = from_json(account_id).unwrap_err();
+            assert!(
+                err.to_string()
+                    .starts_with(&format!("invalid value: \"{}\"", account_id)),
+                "{}",
+                err
+            );
+        }
+
+        // Everything else behaves like `AccountId`
+        for account_id in OK_ACCOUNT_IDS {
+            assert_eq!(from_json(account_id).unwrap(), *account_id);
+        }
+        for account_id in BAD_ACCOUNT_IDS {
+            assert!(from_json(account_id).is_err(), "{}", account_id);
+        }
+
+        // Other targets
+        let boxed: LenientImplicit<Box<AccountIdRef>> =
+            serde_json::from_value(json!("0xB794F5EA0BA39494CE839613FFFBA74279579268")).unwrap();
+        assert_eq!(**boxed, "0xb794f5ea0ba39494ce839613fffba74279579268");
+    }
+
+    #[test]
+    fn test_key_or_account() {
+        use serde::de::value::{BytesDeserializer, Error};
+        use serde::Deserialize;
+
+        let key: Vec<u8> = (0..32).collect();
+        let implicit = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+        // From a sequence of integers
+        let account_id: KeyOrAccount = serde_json::from_value(json!(key)).unwrap();
+        assert_eq!(*account_id, implicit);
+        assert!(account_id.is_near_implicit());
+
+        // From bytes
+        let account_id = KeyOrAccount::deserialize(BytesDeserializer::<Error>::new(&key)).unwrap();
+        assert_eq!(*account_id, implicit);
+
+        // 32 bytes are a key, even if they could be read as an Account ID
+        let ascii = [b'a'; 32];
+        let account_id =
+            KeyOrAccount::deserialize(BytesDeserializer::<Error>::new(&ascii)).unwrap();
+        assert_eq!(*account_id, "61".repeat(32));
+
+        // Other bytes are read as an Account ID
+        let account_id =
+            KeyOrAccount::deserialize(BytesDeserializer::<Error>::new(b"alice.near")).unwrap();
+        assert_eq!(*account_id, "alice.near");
+        assert!(KeyOrAccount::deserialize(BytesDeserializer::<Error>::new(b"Alice.near")).is_err());
+        assert!(KeyOrAccount::deserialize(BytesDeserializer::<Error>::new(&[0xff; 3])).is_err());
+
+        // From strings
+        for account_id in OK_ACCOUNT_IDS {
+            let parsed: KeyOrAccount = serde_json::from_value(json!(account_id)).unwrap();
+            assert_eq!(*parsed, account_id);
+            assert_eq!(serde_json::to_value(&parsed).unwrap(), json!(account_id));
+        }
+        for account_id in BAD_ACCOUNT_IDS {
+            assert!(serde_json::from_value::<KeyOrAccount>(json!(account_id)).is_err());
+        }
+        let parsed: KeyOrAccount = serde_json::from_value(json!(implicit)).unwrap();
+        assert_eq!(AccountId::from(parsed), implicit);
+
+        // Keys have to be exactly 32 bytes long
+        for len in [0, 31, 33] {
+            let key = vec![0_u8; len];
+            assert!(serde_json::from_value::<KeyOrAccount>(json!(key)).is_err());
+        }
+        assert!(serde_json::from_value::<KeyOrAccount>(json!(vec![256; 32])).is_err());
+        assert!(serde_json::from_value::<KeyOrAccount>(json!(42)).is_err());
+    }
+
+    #[test]
+    fn test_len_bounded() {
+        let within = ["aa", "alice.near", "0123456789abcdef0123456789abcdef"];
+        for account_id in within {
+            let bounded: LenBounded<AccountId, 32> =
+                serde_json::from_value(json!(account_id)).unwrap();
+            assert_eq!(*bounded, account_id);
+            assert_eq!(serde_json::to_value(&bounded).unwrap(), json!(account_id));
+
+            let json = json!(account_id).to_string();
+            let bounded: LenBounded<&AccountIdRef, 32> = serde_json::from_str(&json).unwrap();
+            assert_eq!(bounded.into_inner(), account_id);
+        }
+
+        let beyond = [
+            "0123456789abcdef0123456789abcdef0",
+            "0123456789012345678901234567890123456789012345678901234567890123",
+        ];
+        for account_id in beyond {
+            let err =
+                serde_json::from_value::<LenBounded<AccountId, 32>>(json!(account_id)).unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!(
+                    "invalid value: \"{}\", the Account ID is {} bytes long, but the maximum is 32",
+                    account_id,
+                    account_id.len()
+                )
+            );
+            assert_eq!(
+                LenBounded::<AccountId, 32>::new(account_id.parse().unwrap())
+                    .unwrap_err()
+                    .kind(),
+                &ParseErrorKind::TooLong
+            );
+            assert_eq!(
+                LenBounded::<AccountId, 32>::new(account_id.parse().unwrap())
+                    .unwrap_err()
+                    .expected_range(),
+                Some(2..=32)
+            );
+        }
+
+        // The regular validation still applies
+        assert!(serde_json::from_value::<LenBounded<AccountId, 32>>(json!("A")).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "internal_unstable")]
+    fn test_serde_unchecked() {
+        #[derive(Debug, serde::Serialize, serde::Deserialize)]
+        struct Receipt {
+            #[serde(with = "crate::serde_unchecked")]
+            receiver_id: AccountId,
+        }
+
+        for account_id in OK_ACCOUNT_IDS {
+            let receipt: Receipt =
+                serde_json::from_value(json!({ "receiver_id": account_id })).unwrap();
+            assert_eq!(
+                receipt.receiver_id,
+                account_id.parse::<AccountId>().unwrap()
+            );
+            assert_eq!(
+                serde_json::to_value(&receipt).unwrap(),
+                json!({ "receiver_id": account_id })
+            );
+        }
+
+        for account_id in BAD_ACCOUNT_IDS {
+            let value = json!({ "receiver_id": account_id });
+            let strict = serde_json::from_value::<AccountId>(json!(account_id)).unwrap_err();
+            #[cfg(not(feature = "validate-unchecked"))]
+            {
+                let receipt: Receipt = serde_json::from_value(value.clone()).unwrap();
+                assert_eq!(receipt.receiver_id.as_str(), account_id);
+                assert_eq!(serde_json::to_value(&receipt).unwrap(), value);
+                let bytes = bincode::serialize(&receipt).unwrap();
+                let receipt: Receipt = bincode::deserialize(&bytes).unwrap();
+                assert_eq!(receipt.receiver_id.as_str(), account_id);
+                assert!(strict.to_string().starts_with("invalid value"));
+            }
+            #[cfg(feature = "validate-unchecked")]
+            assert_eq!(
+                serde_json::from_value::<Receipt>(value)
+                    .unwrap_err()
+                    .to_string(),
+                strict.to_string()
+            );
+        }
+    }
+
     #[test]
     fn fuzz() {
         bolero::check!().for_each(|input: &[u8]| {
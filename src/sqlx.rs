@@ -0,0 +1,63 @@
+use sqlx::decode::Decode;
+use sqlx::encode::{Encode, IsNull};
+use sqlx::error::BoxDynError;
+use sqlx::types::Type;
+use sqlx::Database;
+
+use crate::AccountId;
+
+// Account IDs are stored as text, in the same way as `Box<str>`.
+
+impl<DB: Database> Type<DB> for AccountId
+where
+    Box<str>: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <Box<str> as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <Box<str> as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB: Database> Encode<'q, DB> for AccountId
+where
+    Box<str>: Encode<'q, DB>,
+{
+    fn encode(self, buf: &mut <DB as Database>::ArgumentBuffer<'q>) -> Result<IsNull, BoxDynError> {
+        Box::<str>::from(self).encode(buf)
+    }
+
+    #[cfg(not(feature = "inline"))]
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        self.0.encode_by_ref(buf)
+    }
+
+    #[cfg(feature = "inline")]
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        Box::<str>::from(self.as_str()).encode(buf)
+    }
+
+    #[cfg(not(feature = "inline"))]
+    fn size_hint(&self) -> usize {
+        self.0.size_hint()
+    }
+}
+
+/// Stored values are validated, and rejected with a [`ParseAccountError`](crate::ParseAccountError) if malformed.
+impl<'r, DB: Database> Decode<'r, DB> for AccountId
+where
+    &'r str: Decode<'r, DB>,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let account_id = <&str as Decode<DB>>::decode(value)?;
+        Ok(account_id.parse()?)
+    }
+}
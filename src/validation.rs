@@ -1,11 +1,13 @@
-use crate::{ParseAccountError, ParseErrorKind};
+use alloc::vec::Vec;
+
+use crate::{AccountIdRef, ImplicitError, ParseAccountError, ParseErrorKind};
 
 /// Shortest valid length for a NEAR Account ID.
 pub const MIN_LEN: usize = 2;
 /// Longest valid length for a NEAR Account ID.
 pub const MAX_LEN: usize = 64;
 
-pub const fn validate_const(account_id: &str) {
+pub const fn validate_or_panic_const(account_id: &str) {
     const fn validate_format_const(id: &[u8], idx: usize, current_char_is_separator: bool) {
         if idx >= id.len() {
             if current_char_is_separator {
@@ -40,63 +42,515 @@ pub const fn validate_const(account_id: &str) {
     validate_format_const(account_id.as_bytes(), 0, false);
 }
 
+/// Returns `true` if the bytes are a valid NEAR Account ID, in a `const` context.
+///
+/// This applies the same rules as [`validate`], without telling which one was broken.
+/// To get a `&AccountIdRef` validated at compile time, use the [`account_id!`](crate::account_id) macro.
+///
+/// ## Examples
+///
+/// ```
+/// use unc_account_id::validate_const;
+///
+/// const IS_VALID: bool = validate_const(b"alice.near");
+/// assert!(IS_VALID);
+///
+/// const _: () = assert!(!validate_const(b"alice..near"));
+/// ```
+pub const fn validate_const(account_id: &[u8]) -> bool {
+    if account_id.len() < MIN_LEN || account_id.len() > MAX_LEN {
+        return false;
+    }
+
+    let mut last_char_is_separator = true;
+    let mut idx = 0;
+    while idx < account_id.len() {
+        let current_char_is_separator = match account_id[idx] {
+            b'a'..=b'z' | b'0'..=b'9' => false,
+            b'-' | b'_' | b'.' => true,
+            _ => return false,
+        };
+        if current_char_is_separator && last_char_is_separator {
+            return false;
+        }
+        last_char_is_separator = current_char_is_separator;
+        idx += 1;
+    }
+
+    !last_char_is_separator
+}
+
+/// Validates a string as a NEAR Account ID, without constructing an [`AccountId`](crate::AccountId).
+///
+/// These are the exact rules applied by [`AccountId`](crate::AccountId) and [`AccountIdRef`](crate::AccountIdRef)
+/// parsing, with the same errors. See the [crate-level docs](crate#account-id-rules) for the rules,
+/// and [Error kind precedence](crate::AccountId#error-kind-precedence).
+///
+/// ## Examples
+///
+/// ```
+/// use unc_account_id::{validate, ParseErrorKind};
+///
+/// assert!(validate("alice.near").is_ok());
+///
+/// let err = validate("alice..near").unwrap_err();
+/// assert_eq!(err.kind(), &ParseErrorKind::RedundantSeparator);
+/// assert_eq!(err.to_string(), "the Account ID has a redundant separator '.' at index 6");
+/// ```
 pub fn validate(account_id: &str) -> Result<(), ParseAccountError> {
-    if account_id.len() < MIN_LEN {
-        Err(ParseAccountError {
-            kind: ParseErrorKind::TooShort,
-            char: None,
-        })
-    } else if account_id.len() > MAX_LEN {
-        Err(ParseAccountError {
-            kind: ParseErrorKind::TooLong,
-            char: None,
-        })
-    } else {
-        // Adapted from https://github.com/near/near-sdk-rs/blob/fd7d4f82d0dfd15f824a1cf110e552e940ea9073/near-sdk/src/environment/env.rs#L819
+    scan(account_id.as_bytes())
+}
 
-        // NOTE: We don't want to use Regex here, because it requires extra time to compile it.
-        // The valid account ID regex is /^(([a-z\d]+[-_])*[a-z\d]+\.)*([a-z\d]+[-_])*[a-z\d]+$/
-        // Instead the implementation is based on the previous character checks.
+/// Checks the separator rules of Account IDs on their own.
+///
+/// Separators (`-`, `_` and `.`) can't start or end an Account ID, nor immediately follow each other.
+/// Violations are reported as [`ParseErrorKind::RedundantSeparator`] at the same position as [`validate`]
+/// would report them: the first separator of the input if it leads, otherwise the second one of a pair,
+/// or the last character if it trails. Any other character is accepted, and the length isn't checked.
+///
+/// ## Examples
+///
+/// ```
+/// use unc_account_id::{check_separators, ParseErrorKind};
+///
+/// assert!(check_separators("my_app-v2").is_ok());
+///
+/// let err = check_separators("a-_b").unwrap_err();
+/// assert_eq!(err.kind(), &ParseErrorKind::RedundantSeparator);
+/// assert_eq!(err.to_string(), "the Account ID has a redundant separator '_' at index 2");
+/// ```
+pub fn check_separators(label: &str) -> Result<(), ParseAccountError> {
+    let is_separator = |c| matches!(c, '-' | '_' | '.');
 
-        // We can safely assume that last char was a separator.
-        let mut last_char_is_separator = true;
+    // Leading separators count as following an imaginary one.
+    let mut last_char_is_separator = true;
+    let mut this = None;
+    for (i, c) in label.char_indices() {
+        this.replace((i, c));
+        let current_char_is_separator = is_separator(c);
+        if current_char_is_separator && last_char_is_separator {
+            return Err(ParseAccountError {
+                kind: ParseErrorKind::RedundantSeparator,
+                char: this,
+                len: None,
+            });
+        }
+        last_char_is_separator = current_char_is_separator;
+    }
 
-        let mut this = None;
-        for (i, c) in account_id.chars().enumerate() {
-            this.replace((i, c));
-            let current_char_is_separator = match c {
-                'a'..='z' | '0'..='9' => false,
-                '-' | '_' | '.' => true,
-                _ => {
-                    return Err(ParseAccountError {
-                        kind: ParseErrorKind::InvalidChar,
-                        char: this,
-                    });
-                }
-            };
-            if current_char_is_separator && last_char_is_separator {
+    match this {
+        Some((_, c)) if is_separator(c) => Err(ParseAccountError {
+            kind: ParseErrorKind::RedundantSeparator,
+            char: this,
+            len: None,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Validates `label` as the name of a new sub-account of `parent`, i.e. `{label}.{parent}`.
+///
+/// This is the check [`AccountIdRef::join`](crate::AccountIdRef::join) applies, with the same errors,
+/// but without allocating. All indices are positions in `label`. The errors are, in order of precedence:
+///
+/// - [`ParseErrorKind::TooLong`] if the sub-account would be longer than [`AccountId::MAX_LEN`](crate::AccountId::MAX_LEN).
+/// - [`ParseErrorKind::TooShort`], with a length of 0, if `label` is empty.
+/// - [`ParseErrorKind::InvalidChar`] for any character not allowed in a label, including `.`.
+/// - [`ParseErrorKind::RedundantSeparator`] for separators that lead, trail, or follow each other.
+///
+/// ## Examples
+///
+/// ```
+/// use unc_account_id::{validate_new_label, AccountIdRef, ParseErrorKind};
+///
+/// let alice = AccountIdRef::new_or_panic("alice.near");
+/// assert!(validate_new_label(alice, "app").is_ok());
+///
+/// let err = validate_new_label(alice, "app-").unwrap_err();
+/// assert_eq!(err.kind(), &ParseErrorKind::RedundantSeparator);
+/// assert_eq!(err.to_string(), "the Account ID has a redundant separator '-' at index 3");
+///
+/// let err = validate_new_label(alice, &"a".repeat(64)).unwrap_err();
+/// assert_eq!(err.kind(), &ParseErrorKind::TooLong);
+/// ```
+pub fn validate_new_label(parent: &AccountIdRef, label: &str) -> Result<(), ParseAccountError> {
+    let len = label.len() + 1 + parent.len();
+    if len > MAX_LEN {
+        return Err(ParseAccountError::too_long(len, MAX_LEN));
+    }
+    validate_segment(label)
+}
+
+/// Validates a single label of an Account ID, such as `app` in `app.alice.near`.
+///
+/// This checks the character set and the separator rules, but not the length of the Account ID
+/// the label ends up in: `a` is a valid segment, even though it is too short to be an Account ID.
+/// Joining a valid segment to a parent with [`AccountIdRef::join`] can then only fail because
+/// the result is too long, which [`validate_new_label`] checks upfront.
+///
+/// Indices are positions in `segment`. The errors are, in order of precedence:
+///
+/// - [`ParseErrorKind::TooShort`], with a length of 0, if `segment` is empty.
+/// - [`ParseErrorKind::InvalidChar`] for any character not allowed in a label, including `.`.
+/// - [`ParseErrorKind::RedundantSeparator`] for separators that lead, trail, or follow each other.
+///
+/// ## Examples
+///
+/// ```
+/// use unc_account_id::{validate_segment, AccountIdRef, ParseErrorKind};
+///
+/// assert!(validate_segment("a").is_ok());
+/// assert!(AccountIdRef::new("a").is_err());
+///
+/// let err = validate_segment("a__b").unwrap_err();
+/// assert_eq!(err.kind(), &ParseErrorKind::RedundantSeparator);
+/// assert_eq!(err.to_string(), "the Account ID has a redundant separator '_' at index 2");
+///
+/// let err = validate_segment("app.alice").unwrap_err();
+/// assert_eq!(err.kind(), &ParseErrorKind::InvalidChar);
+/// ```
+pub fn validate_segment(segment: &str) -> Result<(), ParseAccountError> {
+    if segment.is_empty() {
+        return Err(ParseAccountError::too_short(0));
+    }
+
+    let mut last_char_is_separator = true;
+    let mut this = None;
+    for (i, c) in segment.chars().enumerate() {
+        this.replace((i, c));
+        let current_char_is_separator = match c {
+            'a'..='z' | '0'..='9' => false,
+            '-' | '_' => true,
+            _ => {
                 return Err(ParseAccountError {
-                    kind: ParseErrorKind::RedundantSeparator,
+                    kind: ParseErrorKind::InvalidChar,
                     char: this,
+                    len: None,
                 });
             }
-            last_char_is_separator = current_char_is_separator;
+        };
+        if current_char_is_separator && last_char_is_separator {
+            return Err(ParseAccountError {
+                kind: ParseErrorKind::RedundantSeparator,
+                char: this,
+                len: None,
+            });
         }
+        last_char_is_separator = current_char_is_separator;
+    }
 
-        if last_char_is_separator {
-            return Err(ParseAccountError {
+    if last_char_is_separator {
+        return Err(ParseAccountError {
+            kind: ParseErrorKind::RedundantSeparator,
+            char: this,
+            len: None,
+        });
+    }
+    Ok(())
+}
+
+/// Joins `labels`, leftmost first, and then `parent` with `.` into an [`AccountId`](crate::AccountId),
+/// written out with a single allocation.
+///
+/// Each label is checked like [`validate_new_label`] does, so every constructor built on this reports
+/// the same error for the same label, only with indices shifted to positions in the result.
+/// The errors are, in order of precedence:
+///
+/// - [`ParseErrorKind::TooLong`] if the result would be longer than [`MAX_LEN`].
+/// - The [`validate_segment`] error of the first invalid label.
+/// - [`ParseErrorKind::TooShort`] if the result is shorter than [`MIN_LEN`], including when it is empty.
+pub(crate) fn join_labels<I>(
+    labels: I,
+    parent: Option<&AccountIdRef>,
+) -> Result<crate::AccountId, ParseAccountError>
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut buf = [0; MAX_LEN];
+    let mut len = 0;
+    let mut parts = 0;
+    let mut err = None;
+    let mut push = |part: &str, len: &mut usize| {
+        // Where the part starts in the result, after the separator for all but the first one.
+        let start = if parts == 0 { 0 } else { *len + 1 };
+        let end = start + part.len();
+        if end <= buf.len() {
+            if start > 0 {
+                buf[start - 1] = b'.';
+            }
+            buf[start..end].copy_from_slice(part.as_bytes());
+        }
+        parts += 1;
+        *len = end;
+        start
+    };
+    for label in labels {
+        let label = label.as_ref();
+        let start = push(label, &mut len);
+        if err.is_none() {
+            if let Err(mut label_err) = validate_segment(label) {
+                // Everything before an invalid character is ASCII, so its index is a byte offset as well.
+                if let Some((idx, c)) = label_err.char {
+                    label_err.char = Some((start + idx, c));
+                }
+                err = Some(label_err);
+            }
+        }
+    }
+    if let Some(parent) = parent {
+        push(parent.as_str(), &mut len);
+    }
+
+    if len > MAX_LEN {
+        return Err(ParseAccountError::too_long(len, MAX_LEN));
+    }
+    if let Some(err) = err {
+        return Err(err);
+    }
+    if len < MIN_LEN {
+        return Err(ParseAccountError::too_short(len));
+    }
+    let account_id = core::str::from_utf8(&buf[..len]).expect("valid labels are ASCII");
+    Ok(crate::AccountId(account_id.into()))
+}
+
+/// Validates a string as a NEAR Account ID, reporting every violation instead of just the first one.
+///
+/// The errors come in positional order, after the length violation if there is any. Where [`validate`] fails,
+/// the first error is the same one it returns. Prefer [`validate`] when a single error is enough, since
+/// it stops early.
+///
+/// ## Examples
+///
+/// ```
+/// use unc_account_id::{validate_all, ParseErrorKind};
+///
+/// assert!(validate_all("alice.near").is_ok());
+///
+/// let errors = validate_all("-Ab..c-").unwrap_err();
+/// let kinds: Vec<_> = errors.iter().map(|err| err.kind().clone()).collect();
+/// assert_eq!(
+///     kinds,
+///     [
+///         ParseErrorKind::RedundantSeparator, // '-' at index 0
+///         ParseErrorKind::InvalidChar,        // 'A' at index 1
+///         ParseErrorKind::RedundantSeparator, // '.' at index 4
+///         ParseErrorKind::RedundantSeparator, // '-' at index 6
+///     ]
+/// );
+/// ```
+pub fn validate_all(account_id: &str) -> Result<(), Vec<ParseAccountError>> {
+    let mut errors = Vec::new();
+    if account_id.len() < MIN_LEN {
+        errors.push(ParseAccountError::too_short(account_id.len()));
+    } else if account_id.len() > MAX_LEN {
+        errors.push(ParseAccountError::too_long(account_id.len(), MAX_LEN));
+    }
+
+    // Same checks as in `validate`, but carrying on after each violation.
+    let mut last_char_is_separator = true;
+    let mut this = None;
+    for (i, c) in account_id.char_indices() {
+        this.replace((i, c));
+        let current_char_is_separator = match c {
+            'a'..='z' | '0'..='9' => false,
+            '-' | '_' | '.' => true,
+            _ => {
+                errors.push(ParseAccountError {
+                    kind: ParseErrorKind::InvalidChar,
+                    char: this,
+                    len: None,
+                });
+                // Carry on as if it was a valid non-separator, so that it doesn't cause more errors.
+                false
+            }
+        };
+        if current_char_is_separator && last_char_is_separator {
+            errors.push(ParseAccountError {
                 kind: ParseErrorKind::RedundantSeparator,
                 char: this,
+                len: None,
             });
         }
+        last_char_is_separator = current_char_is_separator;
+    }
+
+    // A trailing separator is only reported once, even if it also follows another separator.
+    let trailing_separator_reported = errors.last().is_some_and(|err| err.char == this);
+    if this.is_some() && last_char_is_separator && !trailing_separator_reported {
+        errors.push(ParseAccountError {
+            kind: ParseErrorKind::RedundantSeparator,
+            char: this,
+            len: None,
+        });
+    }
+
+    if errors.is_empty() {
         Ok(())
+    } else {
+        Err(errors)
     }
 }
 
+/// Parses a newline-delimited list of Account IDs, one line at a time.
+///
+/// Yields the 1-based line number and the parse result of every line, with the trailing `\n` or `\r\n`
+/// trimmed off. A single line buffer is reused throughout, so only the successfully parsed Account IDs
+/// are allocated. Lines that aren't valid UTF-8 fail with [`ParseErrorKind::InvalidUtf8`].
+///
+/// An I/O error from `reader` is yielded as is, and ends the iteration.
+///
+/// ## Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use unc_account_id::{validate_reader, ParseErrorKind};
+///
+/// let list = Cursor::new("alice.near\r\nBob.near\ncarol.near\n");
+/// let results: Vec<_> = validate_reader(list).collect::<Result<_, _>>().unwrap();
+///
+/// assert_eq!(results.len(), 3);
+/// assert_eq!(results[0], (1, Ok("alice.near".parse().unwrap())));
+/// assert_eq!(results[1].0, 2);
+/// assert_eq!(results[1].1.as_ref().unwrap_err().kind(), &ParseErrorKind::InvalidChar);
+/// assert_eq!(results[2], (3, Ok("carol.near".parse().unwrap())));
+/// ```
+#[cfg(feature = "std")]
+pub fn validate_reader<R: std::io::BufRead>(
+    mut reader: R,
+) -> impl Iterator<Item = std::io::Result<(usize, Result<crate::AccountId, ParseAccountError>)>> {
+    let mut line = Vec::new();
+    let mut line_number = 0;
+    let mut failed = false;
+    core::iter::from_fn(move || {
+        if failed {
+            return None;
+        }
+        line.clear();
+        match reader.read_until(b'\n', &mut line) {
+            Ok(0) => None,
+            Ok(_) => {
+                line_number += 1;
+                let account_id = line.strip_suffix(b"\n").unwrap_or(&line);
+                let account_id = account_id.strip_suffix(b"\r").unwrap_or(account_id);
+                let account_id = AccountIdRef::from_utf8(account_id).map(ToOwned::to_owned);
+                Some(Ok((line_number, account_id)))
+            }
+            Err(err) => {
+                failed = true;
+                Some(Err(err))
+            }
+        }
+    })
+}
+
+/// Validates raw bytes as a NEAR Account ID, returning them as a `&str`.
+///
+/// Account IDs are ASCII-only, so UTF-8 validity follows from the character rules and needs no
+/// pass of its own. Errors are the same as those of [`validate`], with indices being byte offsets:
+/// these agree wherever [`validate`] would report an error, since everything before it is ASCII.
+/// A byte that doesn't start a valid UTF-8 sequence is reported as [`ParseErrorKind::InvalidUtf8`],
+/// with `U+FFFD` as its character.
+pub(crate) fn validate_bytes(account_id: &[u8]) -> Result<&str, ParseAccountError> {
+    scan(account_id)?;
+    Ok(core::str::from_utf8(account_id).expect("valid Account IDs are ASCII-only"))
+}
+
+const ALPHANUMERIC: u8 = 0;
+const SEPARATOR: u8 = 1;
+const INVALID: u8 = 2;
+
+const CHAR_CLASS: [u8; 256] = {
+    let mut classes = [INVALID; 256];
+    let mut b = 0;
+    while b < 256 {
+        classes[b] = match b as u8 {
+            b'a'..=b'z' | b'0'..=b'9' => ALPHANUMERIC,
+            b'-' | b'_' | b'.' => SEPARATOR,
+            _ => INVALID,
+        };
+        b += 1;
+    }
+    classes
+};
+
+/// The single pass behind [`validate`] and [`validate_bytes`].
+///
+/// Only bytes are looked at: the first non-ASCII one is an error, and everything before it being
+/// ASCII, its byte offset is also its character index. The character is only decoded then, to report it.
+fn scan(account_id: &[u8]) -> Result<(), ParseAccountError> {
+    fn first_char(bytes: &[u8]) -> Option<char> {
+        if bytes[0].is_ascii() {
+            return Some(char::from(bytes[0]));
+        }
+        // A character takes at most 4 bytes, so there's no need to check the rest.
+        let bytes = &bytes[..bytes.len().min(4)];
+        let valid = match core::str::from_utf8(bytes) {
+            Ok(valid) => valid,
+            Err(err) => core::str::from_utf8(&bytes[..err.valid_up_to()]).unwrap_or_default(),
+        };
+        valid.chars().next()
+    }
+
+    if account_id.len() < MIN_LEN {
+        return Err(ParseAccountError::too_short(account_id.len()));
+    } else if account_id.len() > MAX_LEN {
+        return Err(ParseAccountError::too_long(account_id.len(), MAX_LEN));
+    }
+
+    // Adapted from https://github.com/near/near-sdk-rs/blob/fd7d4f82d0dfd15f824a1cf110e552e940ea9073/near-sdk/src/environment/env.rs#L819
+
+    // NOTE: We don't want to use Regex here, because it requires extra time to compile it.
+    // The valid account ID regex is /^(([a-z\d]+[-_])*[a-z\d]+\.)*([a-z\d]+[-_])*[a-z\d]+$/
+    // Instead the implementation is based on the previous character checks.
+
+    // We can safely assume that last char was a separator.
+    let mut last_char_is_separator = true;
+    for (i, &b) in account_id.iter().enumerate() {
+        let class = CHAR_CLASS[b as usize];
+        if class == INVALID {
+            return Err(match first_char(&account_id[i..]) {
+                Some(c) => ParseAccountError {
+                    kind: ParseErrorKind::InvalidChar,
+                    char: Some((i, c)),
+                    len: None,
+                },
+                None => ParseAccountError {
+                    kind: ParseErrorKind::InvalidUtf8,
+                    char: Some((i, char::REPLACEMENT_CHARACTER)),
+                    len: None,
+                },
+            });
+        }
+        let current_char_is_separator = class == SEPARATOR;
+        if current_char_is_separator && last_char_is_separator {
+            return Err(ParseAccountError {
+                kind: ParseErrorKind::RedundantSeparator,
+                char: Some((i, b as char)),
+                len: None,
+            });
+        }
+        last_char_is_separator = current_char_is_separator;
+    }
+
+    if last_char_is_separator {
+        let i = account_id.len() - 1;
+        return Err(ParseAccountError {
+            kind: ParseErrorKind::RedundantSeparator,
+            char: Some((i, account_id[i] as char)),
+            len: None,
+        });
+    }
+    Ok(())
+}
+
 pub fn is_eth_implicit(account_id: &str) -> bool {
     account_id.len() == 42
         && account_id.starts_with("0x")
-        && account_id[2..].as_bytes().iter().all(|b| matches!(b, b'a'..=b'f' | b'0'..=b'9'))
+        && account_id.as_bytes()[2..]
+            .iter()
+            .all(|b| matches!(b, b'a'..=b'f' | b'0'..=b'9'))
 }
 
 pub fn is_near_implicit(account_id: &str) -> bool {
@@ -107,6 +561,36 @@ pub fn is_near_implicit(account_id: &str) -> bool {
             .all(|b| matches!(b, b'a'..=b'f' | b'0'..=b'9'))
 }
 
+pub fn near_implicit_key(account_id: &str) -> Result<[u8; 32], ImplicitError> {
+    fn hex_value(b: u8) -> Option<u8> {
+        match b {
+            b'0'..=b'9' => Some(b - b'0'),
+            b'a'..=b'f' => Some(b - b'a' + 10),
+            _ => None,
+        }
+    }
+
+    let bytes = account_id.as_bytes();
+    // Validated Account IDs are ASCII, so byte offsets are char indices.
+    if let Some(idx) = bytes.iter().position(|&b| hex_value(b).is_none()) {
+        return if bytes.len() == 64 && !bytes.iter().any(|b| matches!(b, b'-' | b'_' | b'.')) {
+            Err(ImplicitError::InvalidHex(idx, char::from(bytes[idx])))
+        } else {
+            Err(ImplicitError::NotImplicit)
+        };
+    }
+    if bytes.len() != 64 {
+        return Err(ImplicitError::WrongLength(bytes.len()));
+    }
+
+    let mut key = [0; 32];
+    for (byte, pair) in key.iter_mut().zip(bytes.chunks_exact(2)) {
+        // Both digits were checked above.
+        *byte = (hex_value(pair[0]).unwrap_or(0) << 4) | hex_value(pair[1]).unwrap_or(0);
+    }
+    Ok(key)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,10 +617,506 @@ mod tests {
             );
         }
     }
+    #[test]
+    fn test_check_separators() {
+        let cases = [
+            ("a--b", Some((2, '-'))),
+            ("a_-b", Some((2, '-'))),
+            ("a-_b", Some((2, '_'))),
+            ("a._b", Some((2, '_'))),
+            ("-a", Some((0, '-'))),
+            ("_a", Some((0, '_'))),
+            (".a", Some((0, '.'))),
+            ("a-", Some((1, '-'))),
+            ("a_", Some((1, '_'))),
+            ("-", Some((0, '-'))),
+            ("a", None),
+            ("", None),
+            ("a-b_c", None),
+            ("alice.near", None),
+            // Only separators are checked
+            ("Alice-ƒ", None),
+            // Positions are byte offsets
+            ("ƒ--", Some((3, '-'))),
+            ("ƒ-", Some((2, '-'))),
+        ];
+        for (label, expected) in cases {
+            let result = check_separators(label);
+            match expected {
+                Some(char) => assert_eq!(
+                    result,
+                    Err(ParseAccountError {
+                        kind: ParseErrorKind::RedundantSeparator,
+                        char: Some(char),
+                        len: None,
+                    }),
+                    "{:?}",
+                    label
+                ),
+                None => assert_eq!(result, Ok(()), "{:?}", label),
+            }
+        }
+
+        // Agrees with `validate` on everything but lengths and invalid characters
+        for account_id in OK_ACCOUNT_IDS.iter().chain(BAD_ACCOUNT_IDS.iter()) {
+            match validate(account_id) {
+                Err(err) if err.kind() == &ParseErrorKind::RedundantSeparator => {
+                    assert_eq!(check_separators(account_id), Err(err), "{:?}", account_id)
+                }
+                Ok(()) => assert_eq!(check_separators(account_id), Ok(())),
+                Err(_) => {}
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_segment() {
+        let error = |kind, char| {
+            Err(ParseAccountError {
+                kind,
+                char,
+                len: None,
+            })
+        };
+        let cases = [
+            ("0", Ok(())),
+            ("a", Ok(())),
+            ("a-b_c", Ok(())),
+            ("", Err(ParseAccountError::too_short(0))),
+            (
+                "a-",
+                error(ParseErrorKind::RedundantSeparator, Some((1, '-'))),
+            ),
+            (
+                "-a",
+                error(ParseErrorKind::RedundantSeparator, Some((0, '-'))),
+            ),
+            (
+                "a__b",
+                error(ParseErrorKind::RedundantSeparator, Some((2, '_'))),
+            ),
+            (
+                "-",
+                error(ParseErrorKind::RedundantSeparator, Some((0, '-'))),
+            ),
+            ("a.b", error(ParseErrorKind::InvalidChar, Some((1, '.')))),
+            (".", error(ParseErrorKind::InvalidChar, Some((0, '.')))),
+            ("aB", error(ParseErrorKind::InvalidChar, Some((1, 'B')))),
+        ];
+        for (segment, expected) in cases {
+            assert_eq!(validate_segment(segment), expected, "{:?}", segment);
+        }
+
+        // The length of the segment itself isn't limited
+        assert_eq!(validate_segment(&"a".repeat(MAX_LEN + 1)), Ok(()));
+
+        // Every label of a valid Account ID is a valid segment
+        for account_id in OK_ACCOUNT_IDS {
+            for label in account_id.split('.') {
+                assert_eq!(validate_segment(label), Ok(()), "{:?}", label);
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_new_label() {
+        let alice = AccountIdRef::new_or_panic("alice.near");
+        let error = |kind, char| ParseAccountError {
+            kind,
+            char,
+            len: None,
+        };
+        let cases = [
+            ("app", Ok(())),
+            ("a", Ok(())),
+            ("1_4m_n0t-al1c3", Ok(())),
+            ("", Err(ParseAccountError::too_short(0))),
+            (
+                "my.app",
+                Err(error(ParseErrorKind::InvalidChar, Some((2, '.')))),
+            ),
+            (
+                "App",
+                Err(error(ParseErrorKind::InvalidChar, Some((0, 'A')))),
+            ),
+            (
+                "ƒelicia",
+                Err(error(ParseErrorKind::InvalidChar, Some((0, 'ƒ')))),
+            ),
+            (
+                "-app",
+                Err(error(ParseErrorKind::RedundantSeparator, Some((0, '-')))),
+            ),
+            (
+                "app_",
+                Err(error(ParseErrorKind::RedundantSeparator, Some((3, '_')))),
+            ),
+            (
+                "a-_b",
+                Err(error(ParseErrorKind::RedundantSeparator, Some((2, '_')))),
+            ),
+        ];
+        for (label, expected) in cases {
+            assert_eq!(validate_new_label(alice, label), expected, "{:?}", label);
+            // Agrees with `join` on which labels are valid
+            assert_eq!(alice.join(label).is_ok(), expected.is_ok(), "{:?}", label);
+        }
+
+        // The length is checked on the sub-account, before anything else
+        let longest = "a".repeat(MAX_LEN - alice.len() - 1);
+        assert_eq!(validate_new_label(alice, &longest), Ok(()));
+        assert_eq!(
+            validate_new_label(alice, &format!("{}a", longest)),
+            Err(ParseAccountError::too_long(65, MAX_LEN))
+        );
+        assert_eq!(
+            validate_new_label(alice, &format!("-{}", longest)),
+            Err(ParseAccountError::too_long(65, MAX_LEN))
+        );
+    }
+
+    #[test]
+    fn test_validate_all() {
+        let error = |kind, char| ParseAccountError {
+            kind,
+            char,
+            len: None,
+        };
+
+        assert_eq!(
+            validate_all("-Ab..c-"),
+            Err(vec![
+                error(ParseErrorKind::RedundantSeparator, Some((0, '-'))),
+                error(ParseErrorKind::InvalidChar, Some((1, 'A'))),
+                error(ParseErrorKind::RedundantSeparator, Some((4, '.'))),
+                error(ParseErrorKind::RedundantSeparator, Some((6, '-'))),
+            ])
+        );
+        assert_eq!(
+            validate_all("Alice Near@near"),
+            Err(vec![
+                error(ParseErrorKind::InvalidChar, Some((0, 'A'))),
+                error(ParseErrorKind::InvalidChar, Some((5, ' '))),
+                error(ParseErrorKind::InvalidChar, Some((6, 'N'))),
+                error(ParseErrorKind::InvalidChar, Some((10, '@'))),
+            ])
+        );
+        assert_eq!(
+            validate_all("a"),
+            Err(vec![ParseAccountError::too_short(1)])
+        );
+        assert_eq!(
+            validate_all("."),
+            Err(vec![
+                ParseAccountError::too_short(1),
+                error(ParseErrorKind::RedundantSeparator, Some((0, '.'))),
+            ])
+        );
+        assert_eq!(
+            validate_all("alice.."),
+            Err(vec![error(
+                ParseErrorKind::RedundantSeparator,
+                Some((6, '.'))
+            )])
+        );
+        assert_eq!(
+            validate_all("ƒelicia.near."),
+            Err(vec![
+                error(ParseErrorKind::InvalidChar, Some((0, 'ƒ'))),
+                error(ParseErrorKind::RedundantSeparator, Some((13, '.'))),
+            ])
+        );
+        // Indices are byte offsets, past any multi-byte character
+        assert_eq!(
+            validate_all("ñ.ü..near"),
+            Err(vec![
+                error(ParseErrorKind::InvalidChar, Some((0, 'ñ'))),
+                error(ParseErrorKind::InvalidChar, Some((3, 'ü'))),
+                error(ParseErrorKind::RedundantSeparator, Some((6, '.'))),
+            ])
+        );
+        for account_id in ["ƒelicia.near.", "ñ.ü..near", "äA", "😀-"] {
+            for err in validate_all(account_id).unwrap_err() {
+                if let Some((idx, c)) = err.char {
+                    assert_eq!(
+                        account_id[idx..].chars().next(),
+                        Some(c),
+                        "{:?}",
+                        account_id
+                    );
+                }
+            }
+        }
+        let too_long = format!("{}A", "a".repeat(MAX_LEN));
+        assert_eq!(
+            validate_all(&too_long),
+            Err(vec![
+                ParseAccountError::too_long(MAX_LEN + 1, MAX_LEN),
+                error(ParseErrorKind::InvalidChar, Some((MAX_LEN, 'A'))),
+            ])
+        );
+        let too_long = format!("-{}..", "a".repeat(MAX_LEN));
+        assert_eq!(
+            validate_all(&too_long),
+            Err(vec![
+                ParseAccountError::too_long(MAX_LEN + 3, MAX_LEN),
+                error(ParseErrorKind::RedundantSeparator, Some((0, '-'))),
+                error(ParseErrorKind::RedundantSeparator, Some((MAX_LEN + 2, '.'))),
+            ])
+        );
+        assert_eq!(validate_all(""), Err(vec![ParseAccountError::too_short(0)]));
+
+        for account_id in OK_ACCOUNT_IDS {
+            assert_eq!(validate_all(account_id), Ok(()));
+        }
+        for account_id in BAD_ACCOUNT_IDS {
+            assert_eq!(
+                validate_all(account_id).unwrap_err().first(),
+                validate(account_id).err().as_ref(),
+                "{:?}",
+                account_id
+            );
+        }
+    }
+
+    /// `validate` as it was before going byte by byte, decoding every character.
+    fn validate_chars(account_id: &str) -> Result<(), ParseAccountError> {
+        if account_id.len() < MIN_LEN {
+            return Err(ParseAccountError::too_short(account_id.len()));
+        } else if account_id.len() > MAX_LEN {
+            return Err(ParseAccountError::too_long(account_id.len(), MAX_LEN));
+        }
+
+        let mut last_char_is_separator = true;
+        let mut this = None;
+        for (i, c) in account_id.chars().enumerate() {
+            this.replace((i, c));
+            let current_char_is_separator = match c {
+                'a'..='z' | '0'..='9' => false,
+                '-' | '_' | '.' => true,
+                _ => {
+                    return Err(ParseAccountError {
+                        kind: ParseErrorKind::InvalidChar,
+                        char: this,
+                        len: None,
+                    });
+                }
+            };
+            if current_char_is_separator && last_char_is_separator {
+                return Err(ParseAccountError {
+                    kind: ParseErrorKind::RedundantSeparator,
+                    char: this,
+                    len: None,
+                });
+            }
+            last_char_is_separator = current_char_is_separator;
+        }
+
+        if last_char_is_separator {
+            return Err(ParseAccountError {
+                kind: ParseErrorKind::RedundantSeparator,
+                char: this,
+                len: None,
+            });
+        }
+        Ok(())
+    }
+
+    fn check_against_chars(account_id: &str) {
+        let expected = validate_chars(account_id);
+        assert_eq!(validate(account_id), expected, "{:?}", account_id);
+        assert_eq!(
+            validate_bytes(account_id.as_bytes()).map(|_| ()),
+            expected,
+            "{:?}",
+            account_id
+        );
+        assert_eq!(
+            validate_const(account_id.as_bytes()),
+            expected.is_ok(),
+            "{:?}",
+            account_id
+        );
+    }
+
+    #[test]
+    fn test_matches_validate_chars() {
+        // Every string of up to 4 characters over an alphabet covering each character class,
+        // including multi-byte and control characters.
+        const ALPHABET: [char; 14] = [
+            'a', 'z', '0', '9', '-', '_', '.', 'A', '@', ' ', '\0', '\u{7f}', 'é', '€',
+        ];
+        let mut inputs = vec![String::new()];
+        for _ in 0..4 {
+            inputs = inputs
+                .iter()
+                .flat_map(|prefix| {
+                    ALPHABET.iter().map(move |&c| {
+                        let mut input = prefix.clone();
+                        input.push(c);
+                        input
+                    })
+                })
+                .collect();
+            inputs.iter().for_each(|input| check_against_chars(input));
+        }
+
+        // Longer strings around the length bounds, mostly made of valid characters so that
+        // errors show up anywhere in them. A fixed xorshift generator keeps the corpus stable.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0..100_000 {
+            let len = next() as usize % (MAX_LEN + 8);
+            let input: String = (0..len)
+                .map(|_| match next() % 64 {
+                    0 => ALPHABET[next() as usize % ALPHABET.len()],
+                    1..=6 => ['-', '_', '.'][next() as usize % 3],
+                    n => char::from(b"abcdefghijklmnopqrstuvwxyz0123456789"[n as usize % 36]),
+                })
+                .collect();
+            check_against_chars(&input);
+        }
+
+        for account_id in OK_ACCOUNT_IDS.iter().chain(BAD_ACCOUNT_IDS.iter()) {
+            check_against_chars(account_id);
+        }
+    }
+
+    #[test]
+    fn test_length_boundaries() {
+        let too_short = "a".repeat(MIN_LEN - 1);
+        assert_eq!(
+            validate(&too_short).unwrap_err().kind(),
+            &ParseErrorKind::TooShort
+        );
+        assert_eq!(validate("").unwrap_err().kind(), &ParseErrorKind::TooShort);
+        assert!(validate(&"a".repeat(MIN_LEN)).is_ok());
+
+        assert!(validate(&"a".repeat(MAX_LEN)).is_ok());
+        let too_long = "a".repeat(MAX_LEN + 1);
+        assert_eq!(
+            validate(&too_long).unwrap_err().kind(),
+            &ParseErrorKind::TooLong
+        );
+
+        // Display reports the actual length against the violated bound
+        assert_eq!(
+            validate(&too_short).unwrap_err().to_string(),
+            "the Account ID is 1 byte long, but the minimum is 2"
+        );
+        assert_eq!(
+            validate("").unwrap_err().to_string(),
+            "the Account ID is 0 bytes long, but the minimum is 2"
+        );
+        assert_eq!(
+            validate(&too_long).unwrap_err().to_string(),
+            "the Account ID is 65 bytes long, but the maximum is 64"
+        );
+
+        let err = validate(&"a".repeat(71)).unwrap_err();
+        assert_eq!(err.actual_len(), Some(71));
+        assert_eq!(err.expected_range(), Some(MIN_LEN..=MAX_LEN));
+        assert_eq!(
+            err.to_string(),
+            "the Account ID is 71 bytes long, but the maximum is 64"
+        );
+        let err = validate("alice..near").unwrap_err();
+        assert_eq!(err.actual_len(), None);
+        assert_eq!(err.expected_range(), None);
+    }
+
+    #[test]
+    fn test_validate_const() {
+        const _: () = assert!(validate_const(b"near"));
+        const _: () = assert!(!validate_const(b"Near"));
+
+        for account_id in OK_ACCOUNT_IDS {
+            assert!(validate_const(account_id.as_bytes()), "{:?}", account_id);
+        }
+        for account_id in BAD_ACCOUNT_IDS {
+            assert!(!validate_const(account_id.as_bytes()), "{:?}", account_id);
+        }
+
+        // Every string of up to 3 characters over an alphabet covering all character classes
+        let alphabet = ['a', '0', '-', '_', '.', 'A', 'ƒ', ' '];
+        let mut inputs = vec![String::new()];
+        for _ in 0..3 {
+            let longer: Vec<_> = inputs
+                .iter()
+                .flat_map(|s| alphabet.iter().map(move |c| format!("{}{}", s, c)))
+                .collect();
+            inputs.extend(longer);
+        }
+        for input in inputs {
+            assert_eq!(
+                validate_const(input.as_bytes()),
+                validate(&input).is_ok(),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_validate_reader() {
+        use std::io::{BufReader, Cursor, Read};
+
+        let list = b"alice.near\n\nbob..near\r\n0xb794f5ea0ba39494ce839613fffba74279579268\r\nA\n\xffnear\ncarol.near";
+        let results: Vec<_> = validate_reader(Cursor::new(&list[..]))
+            .map(|result| {
+                let (line, account_id) = result.unwrap();
+                (line, account_id.map_err(|err| err.kind().clone()))
+            })
+            .collect();
+        assert_eq!(
+            results,
+            [
+                (1, Ok("alice.near".parse().unwrap())),
+                (2, Err(ParseErrorKind::TooShort)),
+                (3, Err(ParseErrorKind::RedundantSeparator)),
+                (
+                    4,
+                    Ok("0xb794f5ea0ba39494ce839613fffba74279579268"
+                        .parse()
+                        .unwrap())
+                ),
+                (5, Err(ParseErrorKind::TooShort)),
+                (6, Err(ParseErrorKind::InvalidUtf8)),
+                (7, Ok("carol.near".parse().unwrap())),
+            ]
+        );
+        assert_eq!(validate_reader(Cursor::new("")).count(), 0);
+
+        // Reading breaks off after the first I/O error.
+        struct Broken;
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::BrokenPipe.into())
+            }
+        }
+        let mut results =
+            validate_reader(Cursor::new("alice.near\n").chain(BufReader::new(Broken)));
+        assert_eq!(
+            results.next().unwrap().unwrap(),
+            (1, Ok("alice.near".parse().unwrap()))
+        );
+        assert_eq!(
+            results.next().unwrap().unwrap_err().kind(),
+            std::io::ErrorKind::BrokenPipe
+        );
+        assert!(results.next().is_none());
+    }
+
     #[test]
     fn test_is_valid_account_id_const() {
         for account_id in OK_ACCOUNT_IDS {
-            validate_const(account_id);
+            validate_or_panic_const(account_id);
         }
     }
 
@@ -146,7 +1126,7 @@ mod tests {
             // Do not print panic message for caught panic
             std::panic::set_hook(Box::new(|_| {}));
 
-            let result = std::panic::catch_unwind(|| validate_const(account_id));
+            let result = std::panic::catch_unwind(|| validate_or_panic_const(account_id));
 
             // Restore panic hook to default to properly handle assertion failure
             let _ = std::panic::take_hook();
@@ -0,0 +1,138 @@
+//! [`wasm-bindgen`](https://docs.rs/wasm-bindgen) bindings, to validate Account IDs from JavaScript.
+//!
+//! ```js
+//! import { AccountId } from "./pkg/unc_account_id.js";
+//!
+//! const alice = new AccountId("alice.near");
+//! alice.parent()?.asString(); // "near"
+//!
+//! try {
+//!   new AccountId("Alice.near");
+//! } catch (err) {
+//!   err.kind; // "InvalidChar"
+//!   err.position; // 0
+//! }
+//! ```
+
+use alloc::{format, string::String};
+
+use js_sys::{Error, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsValue;
+
+use crate::{AccountId, ParseAccountError};
+
+/// An [`AccountId`] exposed to JavaScript as `AccountId`.
+#[wasm_bindgen(js_name = AccountId)]
+#[derive(Eq, Clone, Debug, PartialEq)]
+pub struct JsAccountId(AccountId);
+
+#[wasm_bindgen(js_class = AccountId)]
+impl JsAccountId {
+    /// Parses an Account ID, throwing a `ParseAccountError` on invalid input.
+    ///
+    /// The error has a `kind` property with the name of the [`ParseErrorKind`](crate::ParseErrorKind),
+    /// such as `"InvalidChar"`, and a `position` property with the byte offset of the offending
+    /// character, or `undefined` for length violations.
+    #[wasm_bindgen(constructor)]
+    pub fn new(account_id: &str) -> Result<JsAccountId, Error> {
+        account_id.parse().map(Self).map_err(js_error)
+    }
+
+    /// Returns the Account ID as a string.
+    #[wasm_bindgen(js_name = asString)]
+    pub fn as_string(&self) -> String {
+        self.0.as_str().into()
+    }
+
+    /// See [`AccountIdRef::is_implicit`](crate::AccountIdRef::is_implicit).
+    #[wasm_bindgen(js_name = isImplicit)]
+    pub fn is_implicit(&self) -> bool {
+        self.0.is_implicit()
+    }
+
+    /// See [`AccountIdRef::is_top_level`](crate::AccountIdRef::is_top_level).
+    #[wasm_bindgen(js_name = isTopLevel)]
+    pub fn is_top_level(&self) -> bool {
+        self.0.is_top_level()
+    }
+
+    /// See [`AccountIdRef::parent`](crate::AccountIdRef::parent).
+    pub fn parent(&self) -> Option<JsAccountId> {
+        self.0.parent().map(|parent| Self(parent.to_owned()))
+    }
+}
+
+impl From<AccountId> for JsAccountId {
+    fn from(account_id: AccountId) -> Self {
+        Self(account_id)
+    }
+}
+
+impl From<JsAccountId> for AccountId {
+    fn from(account_id: JsAccountId) -> Self {
+        account_id.0
+    }
+}
+
+fn js_error(err: ParseAccountError) -> Error {
+    let error = Error::new(&err.to_string());
+    error.set_name("ParseAccountError");
+    let position = err
+        .position()
+        .map_or(JsValue::UNDEFINED, |idx| (idx as u32).into());
+    // Setting properties only fails on frozen objects, which a fresh error isn't.
+    let _ = Reflect::set(&error, &"kind".into(), &format!("{:?}", err.kind()).into());
+    let _ = Reflect::set(&error, &"position".into(), &position);
+    error
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::*;
+
+    fn get(value: &JsValue, key: &str) -> JsValue {
+        Reflect::get(value, &key.into()).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn test_parse() {
+        let app = JsAccountId::new("app.alice.near").unwrap();
+        assert_eq!(app.as_string(), "app.alice.near");
+        assert!(!app.is_implicit());
+        assert!(!app.is_top_level());
+
+        let alice = app.parent().unwrap();
+        assert_eq!(alice.as_string(), "alice.near");
+        let near = alice.parent().unwrap();
+        assert!(near.is_top_level());
+        assert_eq!(near.parent(), None);
+
+        let implicit =
+            JsAccountId::new("98793cd91a3f870fb126f66285808c7e094afcfc4eda8a970f6648cdf0dbd6de")
+                .unwrap();
+        assert!(implicit.is_implicit());
+
+        let account_id: AccountId = app.into();
+        assert_eq!(account_id, "app.alice.near");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_parse_error() {
+        let err = JsValue::from(JsAccountId::new("alice..near").unwrap_err());
+        assert_eq!(get(&err, "name"), "ParseAccountError");
+        assert_eq!(
+            get(&err, "message"),
+            "the Account ID has a redundant separator '.' at index 6"
+        );
+        assert_eq!(get(&err, "kind"), "RedundantSeparator");
+        assert_eq!(get(&err, "position"), 6);
+
+        let err = JsValue::from(JsAccountId::new("a").unwrap_err());
+        assert_eq!(get(&err, "kind"), "TooShort");
+        assert!(get(&err, "position").is_undefined());
+    }
+}
@@ -0,0 +1,72 @@
+use zeroize::Zeroize;
+
+use crate::AccountId;
+
+/// Overwrites the Account ID bytes with zeros, in place, then leaves an empty buffer behind.
+///
+/// No NULs are left to be read back, but an empty `AccountId` isn't a valid Account ID either,
+/// so it should only be dropped afterwards. Prefer [`zeroize::Zeroizing<AccountId>`](zeroize::Zeroizing),
+/// which only wipes the Account ID when it goes out of scope, so the wiped value is never seen.
+/// `AccountId` doesn't implement `ZeroizeOnDrop` itself, since that would prevent moving its buffer out,
+/// e.g. into a `String`.
+///
+/// [`AccountIdRef`](crate::AccountIdRef) doesn't implement `Zeroize`, as it only borrows
+/// the bytes of its owner, which is responsible for wiping them.
+///
+/// ## Examples
+///
+/// ```
+/// use unc_account_id::AccountId;
+/// use zeroize::Zeroizing;
+///
+/// let bob = Zeroizing::new("bob.near".parse::<AccountId>().unwrap());
+/// assert_eq!(*bob, "bob.near");
+/// // Wiped here
+/// drop(bob);
+/// ```
+impl Zeroize for AccountId {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+        // The inline storage resets its own length.
+        #[cfg(not(feature = "inline"))]
+        {
+            self.0 = Default::default();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zeroize::Zeroizing;
+
+    use super::*;
+    use crate::test_data::OK_ACCOUNT_IDS;
+
+    #[test]
+    #[cfg(not(feature = "inline"))]
+    fn test_scrub_in_place() {
+        for account_id in OK_ACCOUNT_IDS {
+            let account_id: AccountId = account_id.parse().unwrap();
+            let mut buf = account_id.0;
+            let (ptr, len) = (buf.as_ptr(), buf.len());
+
+            buf.zeroize();
+            assert_eq!(buf.as_ptr(), ptr);
+            assert_eq!(buf.len(), len);
+            assert!(buf.bytes().all(|b| b == 0));
+        }
+    }
+
+    #[test]
+    fn test_zeroize() {
+        for account_id in OK_ACCOUNT_IDS {
+            let mut account_id: AccountId = account_id.parse().unwrap();
+            account_id.zeroize();
+            // Not a valid Account ID anymore, so look at the storage directly.
+            assert!(account_id.0.is_empty());
+        }
+
+        let alice = Zeroizing::new("alice.near".parse::<AccountId>().unwrap());
+        assert_eq!(alice.as_str(), "alice.near");
+    }
+}